@@ -32,6 +32,54 @@ pub struct Config {
     #[cfg_attr(feature = "cli", arg(long = "output-fragments"))]
     pub output_fragments: Option<Vec<PathBuf>>,
 
+    /// Output file(s) for synthesized schema examples (schema name -> example)
+    #[cfg_attr(feature = "cli", arg(long = "output-examples"))]
+    pub output_examples: Option<Vec<PathBuf>>,
+
+    /// Generate deterministic examples for schemas that lack one
+    #[cfg_attr(
+        feature = "cli",
+        arg(long = "synthesize-examples", num_args = 0..=1, default_missing_value = "true")
+    )]
+    pub synthesize_examples: Option<bool>,
+
+    /// Infer routes for documented handlers from axum Router chains
+    #[cfg_attr(
+        feature = "cli",
+        arg(long = "infer-routes", num_args = 0..=1, default_missing_value = "true")
+    )]
+    pub infer_routes: Option<bool>,
+
+    /// Scoped `@default-*` lines applied to every route (e.g. "@default-security bearerAuth")
+    #[cfg_attr(feature = "cli", arg(long = "route-default"))]
+    pub route_defaults: Option<Vec<String>>,
+
+    /// OperationId strategy: fn, module, method_path or a template ("{module}_{fn}")
+    #[cfg_attr(feature = "cli", arg(long = "operation-id"))]
+    pub operation_id: Option<String>,
+
+    /// Blueprint wrapping 2xx JSON responses (e.g. "ApiResponse" -> $ApiResponse<T>)
+    #[cfg_attr(feature = "cli", arg(long = "response-envelope"))]
+    pub response_envelope: Option<String>,
+
+    /// Schema used as body of non-2xx responses without one
+    #[cfg_attr(feature = "cli", arg(long = "error-envelope"))]
+    pub error_envelope: Option<String>,
+
+    /// Log deprecated operations with their sunset dates and replacements
+    #[cfg_attr(
+        feature = "cli",
+        arg(long = "deprecation-report", num_args = 0..=1, default_missing_value = "true")
+    )]
+    pub deprecation_report: Option<bool>,
+
+    /// Fail when a deprecated operation is past its sunset date
+    #[cfg_attr(
+        feature = "cli",
+        arg(long = "fail-on-sunset", num_args = 0..=1, default_missing_value = "true")
+    )]
+    pub fail_on_sunset: Option<bool>,
+
     /// Path to a configuration file (toml)
     #[cfg_attr(feature = "cli", arg(long = "config"))]
     #[serde(skip)]
@@ -109,6 +157,33 @@ impl Config {
         if let Some(output_fragments) = other.output_fragments {
             self.output_fragments = Some(output_fragments);
         }
+        if let Some(output_examples) = other.output_examples {
+            self.output_examples = Some(output_examples);
+        }
+        if let Some(synthesize_examples) = other.synthesize_examples {
+            self.synthesize_examples = Some(synthesize_examples);
+        }
+        if let Some(infer_routes) = other.infer_routes {
+            self.infer_routes = Some(infer_routes);
+        }
+        if let Some(route_defaults) = other.route_defaults {
+            self.route_defaults = Some(route_defaults);
+        }
+        if let Some(operation_id) = other.operation_id {
+            self.operation_id = Some(operation_id);
+        }
+        if let Some(response_envelope) = other.response_envelope {
+            self.response_envelope = Some(response_envelope);
+        }
+        if let Some(error_envelope) = other.error_envelope {
+            self.error_envelope = Some(error_envelope);
+        }
+        if let Some(deprecation_report) = other.deprecation_report {
+            self.deprecation_report = Some(deprecation_report);
+        }
+        if let Some(fail_on_sunset) = other.fail_on_sunset {
+            self.fail_on_sunset = Some(fail_on_sunset);
+        }
     }
 }
 
@@ -0,0 +1,336 @@
+use regex::Regex;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    Attribute, BinOp, Expr, ImplItemConst, ItemConst, ItemImpl, ItemMod, ItemStatic, Lit, LitStr,
+    UnOp,
+};
+
+/// Collects `const` and `static` items (name + initializer) from a file.
+/// Names are qualified by the inline modules (and impl types) they are declared in
+/// (`inner::LIMIT`, `Page::MAX`). Values are resolved later by [`resolve_constants`],
+/// so items may reference constants declared in other files.
+#[derive(Default)]
+pub struct ConstIndexer {
+    pub pending: Vec<(String, Expr)>,
+    module: Vec<String>,
+}
+
+impl ConstIndexer {
+    fn push(&mut self, name: String, expr: Expr) {
+        let mut path = self.module.clone();
+        path.push(name);
+        self.pending.push((path.join("::"), expr));
+    }
+}
+
+impl<'ast> Visit<'ast> for ConstIndexer {
+    fn visit_item_const(&mut self, i: &'ast ItemConst) {
+        self.push(i.ident.to_string(), (*i.expr).clone());
+        visit::visit_item_const(self, i);
+    }
+
+    fn visit_item_static(&mut self, i: &'ast ItemStatic) {
+        self.push(i.ident.to_string(), (*i.expr).clone());
+        visit::visit_item_static(self, i);
+    }
+
+    fn visit_item_mod(&mut self, i: &'ast ItemMod) {
+        self.module.push(i.ident.to_string());
+        visit::visit_item_mod(self, i);
+        self.module.pop();
+    }
+
+    fn visit_item_impl(&mut self, i: &'ast ItemImpl) {
+        let ty = match &*i.self_ty {
+            syn::Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        };
+        if let Some(ty) = &ty {
+            self.module.push(ty.clone());
+        }
+        visit::visit_item_impl(self, i);
+        if ty.is_some() {
+            self.module.pop();
+        }
+    }
+
+    fn visit_impl_item_const(&mut self, i: &'ast ImplItemConst) {
+        self.push(i.ident.to_string(), i.expr.clone());
+        visit::visit_impl_item_const(self, i);
+    }
+}
+
+/// Parses a file and returns its raw `const` / `static` items.
+pub fn index_file(path: &std::path::Path) -> crate::error::Result<Vec<(String, Expr)>> {
+    let content = std::fs::read_to_string(path)?;
+    let parsed = syn::parse_file(&content).map_err(|e| crate::error::Error::Parse {
+        file: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(index_parsed(&parsed))
+}
+
+/// Raw `const` / `static` items of an already parsed file.
+pub fn index_parsed(file: &syn::File) -> Vec<(String, Expr)> {
+    let mut indexer = ConstIndexer::default();
+    indexer.visit_file(file);
+    indexer.pending
+}
+
+// Prefix of module-qualified keys in the resolved map (`crate::limits::LIMIT`)
+const CRATE_PREFIX: &str = "crate::";
+
+/// Evaluates pending items (in declaration order) until no further progress is made.
+/// Items that are not literal (function calls, struct literals, ...) are skipped.
+///
+/// Every constant is available by its module path (`crate::limits::LIMIT`); the
+/// bare name (`LIMIT`) refers to the first declaration, chosen before evaluating.
+pub fn resolve_constants(pending: Vec<(String, Expr)>) -> HashMap<String, Value> {
+    let mut winners: HashMap<&str, &str> = HashMap::new();
+    for (name, _) in &pending {
+        winners.entry(bare_name(name)).or_insert(name);
+    }
+    let winners: HashSet<String> = winners.into_values().map(str::to_string).collect();
+
+    let mut seen = HashSet::new();
+    let mut remaining: Vec<(String, Expr)> = pending
+        .into_iter()
+        .filter(|(name, _)| seen.insert(name.clone()))
+        .collect();
+    let mut resolved: HashMap<String, Value> = HashMap::new();
+
+    loop {
+        let before = remaining.len();
+        let mut unresolved = Vec::new();
+        for (name, expr) in remaining {
+            let module = name.rsplit_once("::").map_or("", |(module, _)| module);
+            match eval(&expr, &resolved, module) {
+                Some(value) => {
+                    if winners.contains(&name) {
+                        resolved.insert(bare_name(&name).to_string(), value.clone());
+                    }
+                    resolved.insert(format!("{}{}", CRATE_PREFIX, name), value);
+                }
+                None => unresolved.push((name, expr)),
+            }
+        }
+        remaining = unresolved;
+        if remaining.is_empty() || remaining.len() == before {
+            break;
+        }
+    }
+
+    resolved
+}
+
+fn bare_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Looks up a constant by bare name (`LIMIT`) or module path (`limits::LIMIT`,
+/// `crate::limits::LIMIT`). Relative paths are tried from `module` first, then
+/// matched against the end of every module path; ambiguous matches are rejected.
+fn lookup<'a>(
+    constants: &'a HashMap<String, Value>,
+    path: &str,
+    module: &str,
+) -> Option<&'a Value> {
+    let mut segments: Vec<&str> = path.split("::").collect();
+    let absolute = segments.first() == Some(&"crate");
+    segments.retain(|s| !matches!(*s, "crate" | "self" | "super"));
+    let path = segments.join("::");
+    let local = |path: &str| {
+        (!module.is_empty())
+            .then(|| constants.get(&format!("{}{}::{}", CRATE_PREFIX, module, path)))
+            .flatten()
+    };
+
+    if segments.len() == 1 && !absolute {
+        return local(&path).or_else(|| constants.get(&path));
+    }
+    if let Some(value) = constants.get(&format!("{}{}", CRATE_PREFIX, path)) {
+        return Some(value);
+    }
+    if absolute {
+        return None;
+    }
+    if let Some(value) = local(&path) {
+        return Some(value);
+    }
+
+    let suffix = format!("::{}", path);
+    let mut candidates = constants
+        .iter()
+        .filter(|(key, _)| key.starts_with(CRATE_PREFIX) && key.ends_with(&suffix));
+    match (candidates.next(), candidates.next()) {
+        (Some((_, value)), None) => Some(value),
+        (Some((first, _)), Some((second, _))) => {
+            log::warn!(
+                "Constant path '{}' is ambiguous ({}, {}, ...); use its full module path",
+                path,
+                first,
+                second
+            );
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Evaluates a literal expression, including simple arithmetic and references
+/// to already resolved constants.
+pub fn eval_const_expr(expr: &Expr, constants: &HashMap<String, Value>) -> Option<Value> {
+    eval(expr, constants, "")
+}
+
+// Evaluates `expr` declared in `module` (bare names prefer that module).
+fn eval(expr: &Expr, constants: &HashMap<String, Value>, module: &str) -> Option<Value> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Int(i) => i
+                .base10_parse::<i64>()
+                .map(|v| json!(v))
+                .or_else(|_| i.base10_parse::<u64>().map(|v| json!(v)))
+                .ok(),
+            Lit::Float(f) => f.base10_parse::<f64>().ok().map(|v| json!(v)),
+            Lit::Str(s) => Some(json!(s.value())),
+            Lit::Bool(b) => Some(json!(b.value)),
+            Lit::Char(c) => Some(json!(c.value().to_string())),
+            _ => None,
+        },
+        Expr::Paren(p) => eval(&p.expr, constants, module),
+        Expr::Group(g) => eval(&g.expr, constants, module),
+        Expr::Reference(r) => eval(&r.expr, constants, module),
+        Expr::Cast(c) => {
+            let value = eval(&c.expr, constants, module)?;
+            let is_float = matches!(&*c.ty, syn::Type::Path(p) if p.path.is_ident("f32") || p.path.is_ident("f64"));
+            if is_float {
+                value.as_f64().map(|v| json!(v))
+            } else if value.is_f64() {
+                value.as_f64().map(|v| json!(v.trunc() as i64))
+            } else {
+                Some(value)
+            }
+        }
+        Expr::Path(p) => {
+            let path = p
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            lookup(constants, &path, module).cloned()
+        }
+        Expr::Unary(u) => {
+            let value = eval(&u.expr, constants, module)?;
+            match u.op {
+                UnOp::Neg(_) => {
+                    if let Some(i) = value.as_i64() {
+                        Some(json!(-i))
+                    } else {
+                        value.as_f64().map(|f| json!(-f))
+                    }
+                }
+                UnOp::Not(_) => value.as_bool().map(|b| json!(!b)),
+                _ => None,
+            }
+        }
+        Expr::Binary(b) => {
+            let left = eval(&b.left, constants, module)?;
+            let right = eval(&b.right, constants, module)?;
+            eval_binary(&b.op, &left, &right)
+        }
+        _ => None,
+    }
+}
+
+fn eval_binary(op: &BinOp, left: &Value, right: &Value) -> Option<Value> {
+    if let (Some(l), Some(r)) = (left.as_i64(), right.as_i64()) {
+        let v = match op {
+            BinOp::Add(_) => l.checked_add(r)?,
+            BinOp::Sub(_) => l.checked_sub(r)?,
+            BinOp::Mul(_) => l.checked_mul(r)?,
+            BinOp::Div(_) => l.checked_div(r)?,
+            BinOp::Rem(_) => l.checked_rem(r)?,
+            BinOp::Shl(_) => l.checked_shl(u32::try_from(r).ok()?)?,
+            BinOp::Shr(_) => l.checked_shr(u32::try_from(r).ok()?)?,
+            BinOp::BitAnd(_) => l & r,
+            BinOp::BitOr(_) => l | r,
+            BinOp::BitXor(_) => l ^ r,
+            _ => return None,
+        };
+        return Some(json!(v));
+    }
+
+    let (l, r) = (left.as_f64()?, right.as_f64()?);
+    let v = match op {
+        BinOp::Add(_) => l + r,
+        BinOp::Sub(_) => l - r,
+        BinOp::Mul(_) => l * r,
+        BinOp::Div(_) => l / r,
+        BinOp::Rem(_) => l % r,
+        _ => return None,
+    };
+    Some(json!(v))
+}
+
+/// Replaces `{{const NAME}}` placeholders with the resolved literal.
+/// Unknown names are left untouched (and logged).
+pub fn substitute_constants(content: &str, constants: &HashMap<String, Value>) -> String {
+    static CONST_RE: OnceLock<Regex> = OnceLock::new();
+    let const_re = CONST_RE.get_or_init(|| {
+        Regex::new(r"\{\{\s*const\s+([A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*)\s*\}\}")
+            .unwrap()
+    });
+
+    if !content.contains("{{") {
+        return content.to_string();
+    }
+
+    const_re
+        .replace_all(content, |caps: &regex::Captures| {
+            let name = &caps[1];
+            match lookup(constants, name, "") {
+                Some(Value::String(s)) => s.clone(),
+                Some(v) => v.to_string(),
+                None => {
+                    log::warn!("Unknown constant '{}' referenced in documentation", name);
+                    caps[0].to_string()
+                }
+            }
+        })
+        .into_owned()
+}
+
+/// Replaces `{{const NAME}}` placeholders inside the doc comments of a parsed
+/// file. Code (string literals, other attributes) is left untouched.
+pub fn substitute_doc_constants(file: &mut syn::File, constants: &HashMap<String, Value>) {
+    struct DocSubstitution<'a>(&'a HashMap<String, Value>);
+
+    impl VisitMut for DocSubstitution<'_> {
+        fn visit_attribute_mut(&mut self, attr: &mut Attribute) {
+            if !attr.path().is_ident("doc") {
+                return;
+            }
+            if let syn::Meta::NameValue(meta) = &mut attr.meta {
+                if let Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) = &mut meta.value
+                {
+                    let value = doc.value();
+                    if value.contains("{{") {
+                        *doc = LitStr::new(&substitute_constants(&value, self.0), doc.span());
+                    }
+                }
+            }
+            visit_mut::visit_attribute_mut(self, attr);
+        }
+    }
+
+    DocSubstitution(constants).visit_file_mut(file);
+}
@@ -0,0 +1,136 @@
+use serde_yaml_ng::Value;
+
+/// A deprecated operation found in the merged spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedOperation {
+    /// "GET /users/{id}"
+    pub operation: String,
+    pub operation_id: Option<String>,
+    pub since: Option<String>,
+    pub sunset: Option<String>,
+    pub replacement: Option<String>,
+}
+
+impl DeprecatedOperation {
+    /// True if the sunset date (`YYYY-MM-DD`) is today or earlier. The `Sunset`
+    /// header points at midnight UTC, so the endpoint is gone on that day.
+    pub fn sunset_passed(&self, today: &str) -> bool {
+        self.sunset.as_deref().is_some_and(|sunset| sunset <= today)
+    }
+}
+
+impl std::fmt::Display for DeprecatedOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.operation)?;
+        if let Some(id) = &self.operation_id {
+            write!(f, " ({})", id)?;
+        }
+        if let Some(since) = &self.since {
+            write!(f, ", since {}", since)?;
+        }
+        if let Some(sunset) = &self.sunset {
+            write!(f, ", sunset {}", sunset)?;
+        }
+        if let Some(replacement) = &self.replacement {
+            write!(f, ", replaced by {}", replacement)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists every operation in `paths` marked `deprecated: true`, with the
+/// `x-deprecated-since`, `x-sunset` and `x-replaced-by` lifecycle extensions.
+pub fn deprecated_operations(spec: &Value) -> Vec<DeprecatedOperation> {
+    let text = |op: &Value, key: &str| op.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    let mut deprecated = Vec::new();
+    let Some(paths) = spec.get("paths").and_then(|p| p.as_mapping()) else {
+        return deprecated;
+    };
+    for (path, item) in paths {
+        let Some(item) = item.as_mapping() else {
+            continue;
+        };
+        for (method, op) in item {
+            if op.get("deprecated").and_then(|d| d.as_bool()) != Some(true) {
+                continue;
+            }
+            deprecated.push(DeprecatedOperation {
+                operation: format!(
+                    "{} {}",
+                    method.as_str().unwrap_or_default().to_uppercase(),
+                    path.as_str().unwrap_or_default()
+                ),
+                operation_id: text(op, "operationId"),
+                since: text(op, "x-deprecated-since"),
+                sunset: text(op, "x-sunset"),
+                replacement: text(op, "x-replaced-by"),
+            });
+        }
+    }
+    deprecated
+}
+
+/// Today's date (UTC) as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Parses a `YYYY-MM-DD` date into days since the Unix epoch.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if date.len() != 10 || !(1..=12).contains(&month) || day == 0 {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    // Reject overflowing days (2026-02-30)
+    (civil_from_days(days) == (year, month, day)).then_some(days)
+}
+
+/// Formats days since the Unix epoch as an IMF-fixdate (`Thu, 31 Dec 2026 00:00:00 GMT`).
+pub fn http_date(days: i64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{}, {:02} {} {:04} 00:00:00 GMT",
+        WEEKDAYS[days.rem_euclid(7) as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year
+    )
+}
+
+// Howard Hinnant's civil calendar algorithms
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -1,4 +1,5 @@
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{Attribute, Expr, ExprLit, Lit, Meta};
 
 /// Helper to extract doc comments from attributes
@@ -18,6 +19,34 @@ pub fn extract_doc_comments(attrs: &[Attribute]) -> Vec<String> {
     doc_lines
 }
 
+/// Doc comments of a route handler. Each `@example-body` / `@example-response`
+/// line is preceded by its [`example_line_hint`].
+pub fn extract_route_doc_comments(attrs: &[Attribute]) -> Vec<String> {
+    let mut doc_lines = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("doc") {
+            if let Meta::NameValue(meta) = &attr.meta {
+                if let Expr::Lit(expr_lit) = &meta.value {
+                    if let Lit::Str(lit_str) = &expr_lit.lit {
+                        let value = lit_str.value();
+                        doc_lines.extend(example_line_hint(attr, &value));
+                        doc_lines.push(value);
+                    }
+                }
+            }
+        }
+    }
+    doc_lines
+}
+
+/// `@infer example-line <n>` for an example directive doc line: the DSL attaches
+/// the source line to the example placeholder, for errors about the file.
+pub fn example_line_hint(attr: &Attribute, line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    (trimmed.starts_with("@example-body") || trimmed.starts_with("@example-response"))
+        .then(|| format!("@infer example-line {}", attr.span().start().line))
+}
+
 pub fn apply_casing(text: &str, case: &str) -> String {
     match case {
         "lowercase" => text.to_lowercase(),
@@ -208,6 +237,15 @@ use serde_json::{Value, json};
 
 /// Extracts validation attributes from `#[validate(...)]` and maps them to OpenAPI properties.
 pub fn extract_validation(attrs: &[Attribute]) -> Value {
+    extract_validation_with_constants(attrs, &std::collections::HashMap::new())
+}
+
+/// Like [`extract_validation`], but resolves constant references
+/// (e.g. `range(max = MAX_PAGE_SIZE)`) against the indexed constants.
+pub fn extract_validation_with_constants(
+    attrs: &[Attribute],
+    constants: &std::collections::HashMap<String, Value>,
+) -> Value {
     let mut validation_schema = serde_json::Map::new();
 
     for attr in attrs {
@@ -233,22 +271,21 @@ pub fn extract_validation(attrs: &[Attribute]) -> Value {
                                 ) {
                                     for arg in args {
                                         if let Meta::NameValue(nv) = arg {
-                                            if let Expr::Lit(ExprLit {
-                                                lit: Lit::Int(i), ..
-                                            }) = nv.value
+                                            if let Some(val) = crate::constants::eval_const_expr(
+                                                &nv.value, constants,
+                                            )
+                                            .and_then(|v| v.as_u64())
                                             {
-                                                if let Ok(val) = i.base10_parse::<u64>() {
-                                                    if nv.path.is_ident("min") {
-                                                        validation_schema.insert(
-                                                            "minLength".to_string(),
-                                                            json!(val),
-                                                        );
-                                                    } else if nv.path.is_ident("max") {
-                                                        validation_schema.insert(
-                                                            "maxLength".to_string(),
-                                                            json!(val),
-                                                        );
-                                                    }
+                                                if nv.path.is_ident("min") {
+                                                    validation_schema.insert(
+                                                        "minLength".to_string(),
+                                                        json!(val),
+                                                    );
+                                                } else if nv.path.is_ident("max") {
+                                                    validation_schema.insert(
+                                                        "maxLength".to_string(),
+                                                        json!(val),
+                                                    );
                                                 }
                                             }
                                         }
@@ -262,22 +299,17 @@ pub fn extract_validation(attrs: &[Attribute]) -> Value {
                                 ) {
                                     for arg in args {
                                         if let Meta::NameValue(nv) = arg {
-                                            if let Expr::Lit(ExprLit {
-                                                lit: Lit::Int(i), ..
-                                            }) = nv.value
+                                            if let Some(val) = crate::constants::eval_const_expr(
+                                                &nv.value, constants,
+                                            )
+                                            .filter(|v| v.is_number())
                                             {
-                                                if let Ok(val) = i.base10_parse::<i64>() {
-                                                    if nv.path.is_ident("min") {
-                                                        validation_schema.insert(
-                                                            "minimum".to_string(),
-                                                            json!(val),
-                                                        );
-                                                    } else if nv.path.is_ident("max") {
-                                                        validation_schema.insert(
-                                                            "maximum".to_string(),
-                                                            json!(val),
-                                                        );
-                                                    }
+                                                if nv.path.is_ident("min") {
+                                                    validation_schema
+                                                        .insert("minimum".to_string(), json!(val));
+                                                } else if nv.path.is_ident("max") {
+                                                    validation_schema
+                                                        .insert("maximum".to_string(), json!(val));
                                                 }
                                             }
                                         }
@@ -285,15 +317,13 @@ pub fn extract_validation(attrs: &[Attribute]) -> Value {
                                 }
                             }
                             // Helper: #[validate(regex = "path")] or #[validate(pattern = "...")]
-                            Meta::NameValue(nv) => {
-                                if nv.path.is_ident("pattern") {
-                                    if let Expr::Lit(ExprLit {
-                                        lit: Lit::Str(s), ..
-                                    }) = nv.value
-                                    {
-                                        validation_schema
-                                            .insert("pattern".to_string(), json!(s.value()));
-                                    }
+                            Meta::NameValue(nv) if nv.path.is_ident("pattern") => {
+                                if let Expr::Lit(ExprLit {
+                                    lit: Lit::Str(s), ..
+                                }) = nv.value
+                                {
+                                    validation_schema
+                                        .insert("pattern".to_string(), json!(s.value()));
                                 }
                             }
                             _ => {}
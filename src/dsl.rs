@@ -1,3 +1,4 @@
+use crate::index::Registry;
 use crate::type_mapper::map_syn_type_to_openapi;
 use crate::visitor::json_merge;
 use regex::Regex;
@@ -5,15 +6,101 @@ use serde_json::{Value, json};
 use std::collections::HashSet;
 use syn;
 
+/// Context available while compiling a route.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RouteContext<'a> {
+    /// Indexed schemas, used to expand `Query<T>` / `Path<T>` structs into parameters.
+    pub registry: Option<&'a Registry>,
+    /// How default operationIds are derived (function name if `None`).
+    pub operation_ids: Option<&'a OperationIdStrategy>,
+}
+
+/// Derivation of operationIds for routes without `@operation-id`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OperationIdStrategy {
+    /// `list_users` (`Type_method` for impl / trait methods)
+    #[default]
+    FnName,
+    /// `users_admin_list_users`: module path followed by the function name
+    ModulePath,
+    /// `get_users_id`: HTTP method and path slug
+    MethodPath,
+    /// Template with `{fn}`, `{module}`, `{method}` and `{path}` placeholders
+    Template(String),
+}
+
+impl OperationIdStrategy {
+    /// Parses `fn`, `module`, `method_path` or a template containing `{...}`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "fn" | "fn_name" => Some(Self::FnName),
+            "module" | "module_path" => Some(Self::ModulePath),
+            "method_path" => Some(Self::MethodPath),
+            template if template.contains('{') => Some(Self::Template(template.to_string())),
+            _ => None,
+        }
+    }
+
+    /// OperationId of `fn_name` (declared in `module`, `a::b`) served at `method path`.
+    pub fn operation_id(&self, fn_name: &str, module: &str, method: &str, path: &str) -> String {
+        let module = module.replace("::", "_");
+        let path = slug(path);
+        match self {
+            Self::FnName => fn_name.to_string(),
+            Self::ModulePath if module.is_empty() => fn_name.to_string(),
+            Self::ModulePath => format!("{}_{}", module, fn_name),
+            Self::MethodPath if path.is_empty() => method.to_lowercase(),
+            Self::MethodPath => format!("{}_{}", method.to_lowercase(), path),
+            Self::Template(template) => template
+                .replace("{fn}", fn_name)
+                .replace("{module}", &module)
+                .replace("{method}", &method.to_lowercase())
+                .replace("{path}", &path),
+        }
+    }
+}
+
+/// Placeholder operationId of routes declared in module docs (`virtual_route_<line>`).
+pub const VIRTUAL_ROUTE_PREFIX: &str = "virtual_route_";
+
+fn is_virtual_route(operation_id: &str) -> bool {
+    operation_id.starts_with(VIRTUAL_ROUTE_PREFIX)
+}
+
+// `/users/{id}` -> `users_id`
+fn slug(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
 /// Parses a block of doc comments (lines) into an OpenAPI PathItem (YAML/JSON).
-/// Returns Some(yaml_string) if a @route is detected, otherwise None.
+/// Returns Some(yaml_string) if a @route (or @webhook) is detected, otherwise None.
 pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<String> {
+    parse_route_dsl_with(doc_lines, operation_id, &RouteContext::default())
+}
+
+/// Same as [`parse_route_dsl`], resolving signature inference (`@infer` lines)
+/// against the given context.
+pub fn parse_route_dsl_with(
+    doc_lines: &[String],
+    operation_id: &str,
+    ctx: &RouteContext,
+) -> Option<String> {
     // 1. Check if it's a route
     // (Optimization: peek first)
-    if !doc_lines.iter().any(|l| l.trim().starts_with("@route")) {
+    if !doc_lines.iter().any(|l| is_operation_line(l)) {
         return None;
     }
 
+    // 2. Several `@route` lines / `GET|HEAD` each produce their own operation
+    let routes = expand_routes(doc_lines);
+    if routes.len() > 1 {
+        return parse_multi_route(doc_lines, operation_id, &routes, ctx);
+    }
+
     let mut operation = json!({
         "summary": Value::Null,
         "description": Value::Null,
@@ -25,11 +112,40 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
 
     let mut method = String::new();
     let mut path = String::new();
+    // Stacked `@route-prefix` lines (outer modules first)
+    let route_prefix = doc_lines
+        .iter()
+        .filter_map(|l| l.trim().strip_prefix("@route-prefix"))
+        .fold(String::new(), |acc, p| {
+            crate::router::join_paths(&acc, p.trim())
+        });
     let mut description_buffer = Vec::new();
     let mut dsl_override_buffer = Vec::new();
     let mut collecting_openapi = false;
     let mut summary: Option<String> = None;
+    let mut summary_line: Option<String> = None;
+    let mut explicit_summary: Option<String> = None;
+    let mut explicit_description: Option<String> = None;
     let mut declared_path_params = HashSet::new();
+    let mut body_examples: Vec<(String, String, Option<usize>)> = Vec::new();
+    let mut response_examples: Vec<(String, String, String, Option<usize>)> = Vec::new();
+    // Source line of the next example directive (`@infer example-line`)
+    let mut example_line: Option<usize> = None;
+    let mut inferred: Vec<String> = Vec::new();
+    let mut struct_params: Vec<(&str, String)> = Vec::new();
+    let mut encodings: Vec<(String, Value)> = Vec::new();
+    let mut webhook: Option<String> = None;
+    let mut defaults: Vec<&str> = Vec::new();
+    let mut skip_defaults = false;
+    let mut explicit_id = false;
+    let mut module: Vec<&str> = Vec::new();
+    let mut errors: Vec<&str> = Vec::new();
+    let mut stream_events: Vec<(String, Value)> = Vec::new();
+    let mut envelope: Option<&str> = None;
+    let mut error_envelope: Option<&str> = None;
+    let mut raw = false;
+    let mut deprecation_headers: Option<Value> = None;
+    let mut inferred_errors: Vec<&str> = Vec::new();
 
     // Regex for inline path parameters: {name: Type "Desc"}
     let re = Regex::new(r#"\{(\w+)(?::\s*([^"}]+))?(?:\s*"([^"]+)")?\}"#).unwrap();
@@ -44,11 +160,65 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
             collecting_openapi = false;
         }
 
-        if trimmed.starts_with("@route") {
+        if let Some(rest) = trimmed.strip_prefix("@webhook") {
+            // @webhook EventName POST -> root `webhooks` map
+            let parts: Vec<&str> = rest.split_whitespace().collect();
+            if let [name, verb] = parts.as_slice() {
+                webhook = Some(name.to_string());
+                method = verb.to_lowercase();
+            } else {
+                log::error!(
+                    "Malformed @webhook '{}': expected '<Name> <METHOD>'",
+                    rest.trim()
+                );
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@callback") {
+            // @callback onEvent {$request.body#/callbackUrl} POST $Payload ["summary"]
+            if let Some((name, expression, verb, callback_op)) = parse_callback(rest.trim()) {
+                operation["callbacks"][name][expression][verb] = callback_op;
+            }
+        } else if trimmed.starts_with("@route-prefix") {
+            // Resolved above
+        } else if let Some(rest) = trimmed.strip_prefix("@default-") {
+            // Scoped defaults (module docs / config), applied after the explicit lines
+            defaults.push(rest);
+        } else if let Some(rest) = directive(trimmed, "@stream-event") {
+            // @stream-event 200 created: $UserCreated ["desc"] (named SSE event)
+            if let Some((code, event)) = parse_stream_event(rest.trim()) {
+                stream_events.push((code, event));
+            }
+        } else if let Some(rest) = directive(trimmed, "@stream") {
+            // @stream 200: $Event ["desc"] sse|ndjson
+            if let Some((code, response)) = parse_stream(rest.trim()) {
+                merge_response(&mut operation, &code, response);
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@problem") {
+            // @problem 404 ["desc"] | @problem 422 $ValidationProblem ["desc"]
+            if let Some((code, response)) = parse_problem(rest.trim()) {
+                merge_response(&mut operation, &code, response);
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@response-envelope") {
+            // Innermost scope wins (`none` disables it)
+            envelope = Some(rest.trim()).filter(|e| !e.is_empty() && *e != "none");
+        } else if let Some(rest) = trimmed.strip_prefix("@error-envelope") {
+            error_envelope = Some(rest.trim()).filter(|e| !e.is_empty() && *e != "none");
+        } else if trimmed == "@raw" {
+            raw = true;
+        } else if trimmed == "@defaults none" {
+            skip_defaults = true;
+        } else if trimmed.starts_with("@route") {
             let parts: Vec<&str> = trimmed.split_whitespace().collect();
             if parts.len() >= 3 {
                 method = parts[1].to_lowercase();
                 let raw_path = parts[2..].join(" ");
+                // `^/path` opts out of the module prefix
+                let raw_path = match raw_path.strip_prefix('^') {
+                    Some(absolute) => absolute.to_string(),
+                    None if !route_prefix.is_empty() => {
+                        crate::router::join_paths(&route_prefix, &raw_path)
+                    }
+                    None => raw_path,
+                };
 
                 // Route Parser Logic (from visitor.rs)
                 let mut new_path = String::new();
@@ -98,6 +268,58 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
                 new_path.push_str(&raw_path[last_end..]);
                 path = new_path;
             }
+        } else if let Some(rest) = trimmed.strip_prefix("@operation-id") {
+            let id = rest.trim();
+            if id.is_empty() {
+                log::warn!("@operation-id without a value");
+            } else {
+                operation["operationId"] = json!(id);
+                explicit_id = true;
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@summary") {
+            explicit_summary = Some(rest.trim().to_string());
+        } else if let Some(rest) = trimmed.strip_prefix("@description") {
+            explicit_description = Some(rest.trim().to_string());
+        } else if trimmed == "@deprecated" || trimmed.starts_with("@deprecated ") {
+            // @deprecated [since=YYYY-MM-DD] [sunset=YYYY-MM-DD] [replacement=operationId]
+            operation["deprecated"] = json!(true);
+            deprecation_headers =
+                parse_deprecation(&trimmed["@deprecated".len()..], &mut operation);
+        } else if let Some(rest) = trimmed.strip_prefix("@external-docs") {
+            // @external-docs URL ["desc"]
+            let rest = rest.trim();
+            let (url, desc) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let mut docs = json!({ "url": url });
+            let desc = desc.trim().trim_matches('"');
+            if !desc.is_empty() {
+                docs["description"] = json!(desc);
+            }
+            operation["externalDocs"] = docs;
+        } else if let Some(rest) = trimmed.strip_prefix("@server") {
+            // @server URL ["desc"]
+            let rest = rest.trim();
+            let (url, desc) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let mut server = json!({ "url": url });
+            let desc = desc.trim().trim_matches('"');
+            if !desc.is_empty() {
+                server["description"] = json!(desc);
+            }
+            if operation.get("servers").is_none() {
+                operation["servers"] = json!([]);
+            }
+            if let Some(Value::Array(servers)) = operation.get_mut("servers") {
+                servers.push(server);
+            }
+        } else if trimmed.starts_with("@x-") {
+            // @x-<name> <yaml> (vendor extension, a bare flag means `true`)
+            let rest = &trimmed[1..];
+            let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let value = value.trim();
+            operation[key] = if value.is_empty() {
+                json!(true)
+            } else {
+                serde_yaml_ng::from_str(value).unwrap_or_else(|_| json!(value))
+            };
         } else if trimmed.starts_with("@tag") {
             let tags: Vec<String> = trimmed
                 .strip_prefix("@tag")
@@ -107,6 +329,12 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
                 .filter(|s| !s.is_empty())
                 .collect();
             operation["tags"] = json!(tags);
+        } else if let Some((location, rest)) = ["query", "header", "cookie"]
+            .into_iter()
+            .find_map(|loc| Some((loc, trimmed.strip_prefix(&format!("@{}-params", loc))?)))
+        {
+            // Struct expansion, resolved after the single params
+            struct_params.push((location, rest.trim().to_string()));
         } else if trimmed.starts_with("@path-param")
             || trimmed.starts_with("@query-param")
             || trimmed.starts_with("@header-param")
@@ -123,220 +351,114 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
                 ("cookie", trimmed.strip_prefix("@cookie-param").unwrap())
             };
 
-            let rest = rest.trim();
-            if let Some(colon_idx) = rest.find(':') {
-                let name = rest[..colon_idx].trim();
-                let type_part = rest[colon_idx + 1..].trim();
-
-                let tokens_vec: Vec<&str> = type_part.split_whitespace().collect();
-                let first = tokens_vec.first().copied().unwrap_or("");
-
-                let (type_def, start_idx) = if first == "deprecated"
-                    || first == "required"
-                    || first.starts_with("example=")
-                    || first.starts_with('"')
-                {
-                    ("String", 0)
-                } else if !tokens_vec.is_empty() {
-                    (first, 1)
-                } else {
-                    ("String", 0)
-                };
-
-                let (schema, mut is_required) =
-                    if let Ok(ty) = syn::parse_str::<syn::Type>(type_def) {
-                        map_syn_type_to_openapi(&ty)
-                    } else {
-                        (json!({ "type": "string" }), true)
-                    };
-
-                let mut deprecated = false;
-                let mut example = None;
-                let mut desc = None;
-
-                let mut desc_tokens = Vec::new();
-                let mut in_desc = false;
-
-                // Attributes check in tokens
-                for token in tokens_vec.iter().skip(start_idx) {
-                    if in_desc {
-                        desc_tokens.push(*token);
-                        continue;
-                    }
-
-                    if *token == "deprecated" {
-                        deprecated = true;
-                    } else if *token == "required" {
-                        is_required = true;
-                    } else if token.starts_with("example=") {
-                        let val = token.strip_prefix("example=").unwrap().trim_matches('"');
-                        example = Some(val.to_string());
-                    } else if token.starts_with('"') {
-                        in_desc = true;
-                        desc_tokens.push(*token);
-                    }
-                }
-
-                if !desc_tokens.is_empty() {
-                    desc = Some(desc_tokens.join(" ").trim_matches('"').to_string());
-                }
-
-                let mut param_obj = json!({
-                    "name": name,
-                    "in": param_type,
-                    "required": is_required,
-                    "schema": schema
-                });
-
-                if deprecated {
-                    param_obj
-                        .as_object_mut()
-                        .unwrap()
-                        .insert("deprecated".to_string(), json!(true));
-                }
-                if let Some(ex) = example {
-                    param_obj
-                        .as_object_mut()
-                        .unwrap()
-                        .insert("example".to_string(), json!(ex));
-                }
+            if let Some((name, spec)) = parse_param_spec(rest.trim()) {
+                let mut param_obj = json!({ "name": name, "in": param_type });
+                json_merge(&mut param_obj, spec);
                 if param_type == "path" {
                     declared_path_params.insert(name.to_string());
-                    param_obj
-                        .as_object_mut()
-                        .unwrap()
-                        .insert("required".to_string(), json!(true));
-                }
-                if let Some(d) = desc {
-                    param_obj
-                        .as_object_mut()
-                        .unwrap()
-                        .insert("description".to_string(), json!(d));
+                    param_obj["required"] = json!(true);
                 }
 
                 if let Value::Array(params) = operation.get_mut("parameters").unwrap() {
                     params.push(param_obj);
                 }
             }
+        } else if let Some(rest) = trimmed.strip_prefix("@response-header") {
+            // @response-header 201 Location: String "URL of created resource"
+            let rest = rest.trim();
+            if let Some((code, spec)) = rest.split_once(char::is_whitespace) {
+                if let Some((name, header)) = parse_param_spec(spec.trim()) {
+                    let mut response = json!({ "description": "", "headers": {} });
+                    response["headers"][name] = header;
+                    merge_response(&mut operation, &normalize_status(code), response);
+                }
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@link") {
+            // @link 201 GetUser operationId=get_user id=$response.body#/id
+            if let Some((code, name, link)) = parse_link(rest.trim()) {
+                let mut response = json!({ "description": "", "links": {} });
+                response["links"][name] = link;
+                merge_response(&mut operation, &code, response);
+            }
         } else if trimmed.starts_with("@body") {
-            // ... Body Logic (Ported) ...
+            // @body $Type [mime, ...] (repeated lines add alternative content types)
             let rest = trimmed.strip_prefix("@body").unwrap().trim();
-            let parts: Vec<&str> = rest.split_whitespace().collect();
-            if !parts.is_empty() {
-                let schema_ref = parts[0];
-                let mime = if parts.len() > 1 {
-                    parts[1]
-                } else {
-                    "application/json"
-                };
-
-                let is_std_generic = schema_ref.starts_with("Option<")
-                    || schema_ref.starts_with("Vec<")
-                    || schema_ref.starts_with("Box<")
-                    || schema_ref.starts_with("Arc<")
-                    || schema_ref.starts_with("Rc<")
-                    || schema_ref.starts_with("Cow<");
-
-                let schema = if !is_std_generic
-                    && (schema_ref.contains('<')
-                        || (schema_ref.starts_with('$') && schema_ref.contains('<')))
-                {
-                    json!({ "$ref": schema_ref })
-                } else if let Ok(ty) = syn::parse_str::<syn::Type>(schema_ref) {
-                    map_syn_type_to_openapi(&ty).0
-                } else if let Some(stripped) = schema_ref.strip_prefix('$') {
-                    json!({ "$ref": format!("#/components/schemas/{}", stripped) })
-                } else {
-                    json!({ "$ref": format!("#/components/schemas/{}", schema_ref) })
-                };
+            let (schema_ref, mimes) = split_type_token(rest);
+            if !schema_ref.is_empty() {
+                let mut mimes: Vec<&str> = mimes
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|m| !m.is_empty())
+                    .collect();
+                if mimes.is_empty() {
+                    mimes.push("application/json");
+                }
 
-                operation["requestBody"] = json!({
-                    "content": { mime: { "schema": schema } }
-                });
+                if operation.get("requestBody").is_none() {
+                    operation["requestBody"] = json!({ "content": {} });
+                }
+                for mime in mimes {
+                    let schema = if is_form_mime(mime) {
+                        form_schema(schema_ref, mime, ctx)
+                    } else {
+                        type_schema(schema_ref)
+                    };
+                    operation["requestBody"]["content"][mime] = json!({ "schema": schema });
+                }
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@encoding") {
+            // @encoding <part> contentType=... | @encoding <part> header Name: Type "desc"
+            if let Some((part, encoding)) = parse_encoding(rest.trim()) {
+                encodings.push((part, encoding));
             }
         } else if trimmed.starts_with("@return") {
-            // ... Return Logic (Ported) ...
+            // @return <code>: [Type] ["desc"] [mime, ...]
             let rest = trimmed.strip_prefix("@return").unwrap().trim();
-            if let Some(colon_idx) = rest.find(':') {
-                let code = rest[..colon_idx].trim();
-                let residue = rest[colon_idx + 1..].trim();
-
-                let (type_str, desc, is_unit) = if residue.starts_with('"') {
-                    ("()", Some(residue.trim_matches('"').to_string()), true)
-                } else if residue.ends_with('"') && residue.contains(" \"") {
-                    let desc_start = residue.rfind(" \"").unwrap();
-                    (
-                        residue[..desc_start].trim(),
-                        Some(residue[desc_start + 2..residue.len() - 1].to_string()),
-                        false,
-                    )
-                } else if let Some(quote_start) = residue.find('"') {
-                    (
-                        residue[..quote_start].trim(),
-                        Some(residue[quote_start + 1..residue.len() - 1].to_string()),
-                        false,
-                    )
-                } else {
-                    (residue, None, false)
-                };
-
-                let effective_unit = is_unit || type_str == "()" || type_str == "unit";
-                let is_std_generic = type_str.starts_with("Option<")
-                    || type_str.starts_with("Vec<")
-                    || type_str.starts_with("Box<")
-                    || type_str.starts_with("Arc<")
-                    || type_str.starts_with("Rc<")
-                    || type_str.starts_with("Cow<");
-
-                let schema = if effective_unit {
-                    json!({})
-                } else if type_str.starts_with('{') {
-                    serde_yaml_ng::from_str(type_str)
-                        .unwrap_or_else(|_| json!({ "$ref": type_str }))
-                } else if !is_std_generic
-                    && (type_str.contains('<')
-                        || (type_str.starts_with('$') && type_str.contains('<')))
-                {
-                    json!({ "$ref": type_str })
-                } else if let Ok(ty) = syn::parse_str::<syn::Type>(type_str) {
-                    map_syn_type_to_openapi(&ty).0
-                } else if let Some(stripped) = type_str.strip_prefix('$') {
-                    json!({ "$ref": format!("#/components/schemas/{}", stripped) })
-                } else if type_str == "String" || type_str == "str" {
-                    json!({ "type": "string" })
-                } else {
-                    json!({ "$ref": format!("#/components/schemas/{}", type_str) })
-                };
-
-                let mut resp_obj = json!({ "description": desc.unwrap_or_default() });
-                if !effective_unit {
-                    resp_obj["content"] = json!({ "application/json": { "schema": schema } });
-                }
-
-                if let Value::Object(responses) = operation.get_mut("responses").unwrap() {
-                    responses.insert(code.to_string(), resp_obj);
-                }
+            if let Some((code, response)) = parse_return(rest) {
+                merge_response(&mut operation, &code, response);
             }
-        } else if trimmed.starts_with("@security") {
-            // ... Security Logic ...
-            let rest = trimmed.strip_prefix("@security").unwrap().trim();
-            let (scheme, scopes) = if let Some(paren_start) = rest.find('(') {
-                let name = rest[..paren_start].trim();
-                let inner = &rest[paren_start + 1..rest.len() - 1];
-                let s: Vec<String> = inner
-                    .split(',')
-                    .map(|s| s.trim().trim_matches('"').to_string())
-                    .collect();
-                (name, s)
-            } else {
-                (rest, vec![])
-            };
-
+        } else if let Some(rest) = trimmed.strip_prefix("@security") {
+            // @security scheme("scope", ...) | @security none (public route)
             if operation.get("security").is_none() {
                 operation["security"] = json!([]);
             }
-            if let Value::Array(sec) = operation.get_mut("security").unwrap() {
-                sec.push(json!({ scheme: scopes }));
+            let rest = rest.trim();
+            if rest != "none" {
+                if let Value::Array(sec) = operation.get_mut("security").unwrap() {
+                    sec.push(parse_security(rest));
+                }
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("@errors") {
+            // @errors $ApiError (error enum with `@openapi status` variants)
+            errors.extend(rest.split(',').map(|e| e.trim().trim_start_matches('$')));
+        } else if let Some(rest) = trimmed.strip_prefix("@infer errors ") {
+            // Error type of a `Result` return, used without explicit `@errors`
+            inferred_errors.push(rest.trim());
+        } else if let Some(rest) = trimmed.strip_prefix("@infer module ") {
+            // File module path (scanner) followed by inline modules (visitor)
+            module.push(rest.trim());
+        } else if let Some(rest) = trimmed.strip_prefix("@infer example-line ") {
+            example_line = rest.trim().parse().ok();
+        } else if let Some(rest) = trimmed.strip_prefix("@infer ") {
+            // Derived from the handler signature, resolved after explicit lines
+            inferred.push(rest.trim().to_string());
+        } else if trimmed.starts_with("@example-body") {
+            // @example-body [name] path
+            let rest = trimmed.strip_prefix("@example-body").unwrap().trim();
+            if let Some((name, file)) = parse_example_ref(rest) {
+                body_examples.push((name, file, example_line.take()));
+            }
+        } else if trimmed.starts_with("@example-response") {
+            // @example-response 200 [name] path
+            let rest = trimmed.strip_prefix("@example-response").unwrap().trim();
+            if let Some((code, tail)) = rest.split_once(char::is_whitespace) {
+                if let Some((name, file)) = parse_example_ref(tail.trim()) {
+                    response_examples.push((
+                        code.trim_end_matches(':').to_string(),
+                        name,
+                        file,
+                        example_line.take(),
+                    ));
+                }
             }
         } else if !trimmed.starts_with('@') {
             // Override Logic
@@ -356,12 +478,20 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
                 dsl_override_buffer.push(line.to_string());
             } else if summary.is_none() {
                 summary = Some(trimmed.to_string());
+                summary_line = Some(line.to_string());
             } else {
                 description_buffer.push(line.to_string());
             }
         }
     }
 
+    // Explicit @summary turns the first free-text line into description
+    if let Some(s) = explicit_summary {
+        if let Some(line) = summary_line {
+            description_buffer.insert(0, line);
+        }
+        summary = Some(s);
+    }
     if let Some(s) = summary {
         operation["summary"] = json!(s);
     }
@@ -387,6 +517,74 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
 
         operation["description"] = json!(cleaned_desc.join("\n"));
     }
+    if let Some(d) = explicit_description {
+        operation["description"] = json!(d);
+    }
+
+    // Part Encodings (form bodies only)
+    if !encodings.is_empty() {
+        if let Some(Value::Object(content)) = operation
+            .get_mut("requestBody")
+            .and_then(|b| b.get_mut("content"))
+        {
+            for (mime, media) in content.iter_mut() {
+                if !is_form_mime(mime) {
+                    continue;
+                }
+                for (part, encoding) in &encodings {
+                    if media["encoding"].get(part).is_none() {
+                        media["encoding"][part] = json!({});
+                    }
+                    json_merge(&mut media["encoding"][part], encoding.clone());
+                }
+            }
+        }
+    }
+
+    // Struct Params (@query-params $Filter)
+    for (location, name) in &struct_params {
+        expand_struct_params(&mut operation, location, name, ctx);
+    }
+
+    // Signature Inference (explicit lines take precedence)
+    if !inferred.is_empty() {
+        apply_inferred(
+            &mut operation,
+            &path,
+            &mut declared_path_params,
+            &inferred,
+            ctx,
+        );
+    }
+
+    // External Examples (loaded by the scanner)
+    if !body_examples.is_empty() {
+        if operation.get("requestBody").is_none() {
+            operation["requestBody"] = json!({ "content": { "application/json": {} } });
+        }
+        if let Some(Value::Object(content)) = operation["requestBody"].get_mut("content") {
+            for media in content.values_mut() {
+                for (name, file, line) in &body_examples {
+                    media["examples"][name] = example_placeholder(file, *line);
+                }
+            }
+        }
+    }
+    for (code, name, file, line) in &response_examples {
+        let responses = operation.get_mut("responses").unwrap();
+        if responses.get(code).is_none() {
+            responses[code] = json!({ "description": "" });
+        }
+        let response = &mut responses[code];
+        if response.get("content").is_none() {
+            response["content"] = json!({ "application/json": {} });
+        }
+        if let Some(Value::Object(content)) = response.get_mut("content") {
+            for media in content.values_mut() {
+                media["examples"][name] = example_placeholder(file, *line);
+            }
+        }
+    }
 
     // Merge Overrides
     if !dsl_override_buffer.is_empty() {
@@ -398,6 +596,114 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
         }
     }
 
+    // OperationId Strategy (explicit `@operation-id` wins)
+    if !explicit_id && (ctx.operation_ids.is_some() || is_virtual_route(operation_id)) {
+        let strategy = ctx.operation_ids.cloned().unwrap_or_default();
+        let target = webhook.as_deref().unwrap_or(&path);
+        // Routes in module docs have no function: name them after method and path
+        let fn_name = if is_virtual_route(operation_id) {
+            OperationIdStrategy::MethodPath.operation_id("", "", &method, target)
+        } else {
+            operation_id.to_string()
+        };
+        operation["operationId"] =
+            json!(strategy.operation_id(&fn_name, &module.join("::"), &method, target));
+    }
+
+    // Named SSE Events: the item schema becomes a `oneOf` of the events
+    let mut event_codes: Vec<&str> = Vec::new();
+    for (code, _) in &stream_events {
+        if !event_codes.contains(&code.as_str()) {
+            event_codes.push(code);
+        }
+    }
+    for code in event_codes {
+        // A typed `@stream <code>: $Item sse` item joins the events instead of being replaced
+        let plain = operation["responses"]
+            .get(code)
+            .and_then(|r| r["content"][SSE_MIME].get(ITEM_SCHEMA_KEY))
+            .filter(|item| item["properties"]["data"].get("contentSchema").is_some())
+            .cloned();
+        let events: Vec<Value> = plain
+            .into_iter()
+            .chain(
+                stream_events
+                    .iter()
+                    .filter(|(c, _)| c == code)
+                    .map(|(_, event)| event.clone()),
+            )
+            .collect();
+        let response = json!({
+            "description": "",
+            "content": {
+                SSE_MIME: { "schema": { "type": "string" }, ITEM_SCHEMA_KEY: { "oneOf": events } }
+            }
+        });
+        merge_response(&mut operation, code, response);
+    }
+
+    // Error Enums (explicit `@return` lines win)
+    let explicit_errors = !errors.is_empty();
+    let error_types = if explicit_errors {
+        errors
+    } else {
+        inferred_errors
+    };
+    for name in error_types.into_iter().filter(|n| !n.is_empty()) {
+        match ctx.registry.and_then(|r| r.error_responses.get(name)) {
+            Some(content) => {
+                if let Ok(Value::Object(responses)) = serde_json::from_str::<Value>(content) {
+                    for (code, response) in responses {
+                        if operation["responses"].get(&code).is_none() {
+                            operation["responses"][code] = response;
+                        }
+                    }
+                }
+            }
+            None if explicit_errors => {
+                log::warn!("@errors: '{}' has no `@openapi status` variants", name)
+            }
+            None => {}
+        }
+    }
+
+    // Scoped Defaults (anything the route declares itself wins)
+    if !skip_defaults && !defaults.is_empty() {
+        apply_defaults(&mut operation, &defaults);
+    }
+
+    // Deprecation / Sunset headers on every response
+    if let Some(headers) = deprecation_headers {
+        if let Some(Value::Object(responses)) = operation.get_mut("responses") {
+            for response in responses.values_mut() {
+                for (name, header) in headers.as_object().into_iter().flatten() {
+                    if response["headers"].get(name).is_none() {
+                        response["headers"][name] = header.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    // Response Envelopes (`@raw` opts out)
+    if !raw && (envelope.is_some() || error_envelope.is_some()) {
+        apply_envelopes(&mut operation, envelope, error_envelope, ctx.registry)?;
+    }
+
+    if let Some(name) = webhook {
+        if method.is_empty() {
+            return None;
+        }
+        if let Value::Object(map) = &mut operation {
+            map.retain(|_, v| !v.is_null());
+        }
+        let mut item = json!({ "webhooks": { name: { method: operation } } });
+        add_problem_component(&mut item, ctx);
+        return serde_yaml_ng::to_string(&item)
+            .ok()
+            .map(|generated| generated.trim_start_matches("---\n").to_string());
+    }
+
     // Validation (Path Params)
     let validation_re = Regex::new(r"\{(\w+)\}").unwrap();
     if !method.is_empty() && !path.is_empty() {
@@ -433,7 +739,8 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
         let mut path_map = serde_json::Map::new();
         path_map.insert(path, Value::Object(method_map));
 
-        let path_item = json!({ "paths": Value::Object(path_map) });
+        let mut path_item = json!({ "paths": Value::Object(path_map) });
+        add_problem_component(&mut path_item, ctx);
 
         if let Ok(generated) = serde_yaml_ng::to_string(&path_item) {
             return Some(generated.trim_start_matches("---\n").to_string());
@@ -442,3 +749,1364 @@ pub fn parse_route_dsl(doc_lines: &[String], operation_id: &str) -> Option<Strin
 
     None
 }
+
+/// Parses the lifecycle attributes of `@deprecated` into `x-deprecated-since`,
+/// `x-sunset` and `x-replaced-by`. Returns the `Deprecation` (RFC 9745) and
+/// `Sunset` (RFC 8594) response headers when a lifecycle attribute is present.
+fn parse_deprecation(rest: &str, operation: &mut Value) -> Option<Value> {
+    let mut headers = json!({});
+    for token in rest.split_whitespace() {
+        let Some((key, value)) = token.split_once('=') else {
+            continue;
+        };
+        match key {
+            "since" | "sunset" => {
+                let Some(days) = crate::deprecation::parse_date(value) else {
+                    log::warn!("Invalid @deprecated date '{}' (expected YYYY-MM-DD)", token);
+                    continue;
+                };
+                if key == "since" {
+                    operation["x-deprecated-since"] = json!(value);
+                    headers["Deprecation"] = json!({
+                        "description": format!("Deprecated since {}", value),
+                        "schema": { "type": "string" },
+                        "example": format!("@{}", days * 86_400)
+                    });
+                } else {
+                    operation["x-sunset"] = json!(value);
+                    headers["Sunset"] = json!({
+                        "description": format!("The operation is removed on {}", value),
+                        "schema": { "type": "string" },
+                        "example": crate::deprecation::http_date(days)
+                    });
+                }
+            }
+            "replacement" => operation["x-replaced-by"] = json!(value),
+            _ => log::warn!("Unknown @deprecated attribute '{}'", token),
+        }
+    }
+    // Plain `@deprecated` only sets the flag
+    if ["x-deprecated-since", "x-sunset", "x-replaced-by"]
+        .iter()
+        .all(|key| operation.get(*key).is_none())
+    {
+        return None;
+    }
+    if headers.get("Deprecation").is_none() {
+        headers["Deprecation"] = json!({
+            "description": "The operation is deprecated",
+            "schema": { "type": "string" }
+        });
+    }
+    Some(headers)
+}
+
+/// Wraps 2xx `application/json` schemas in the envelope (`$Envelope<T>` for
+/// component schemas, instantiated by the Monomorphizer; an inline instance for
+/// anything else) and gives body-less 4xx / 5xx / `default` responses the error
+/// envelope. Returns `None` when a 2xx body cannot be wrapped.
+fn apply_envelopes(
+    operation: &mut Value,
+    envelope: Option<&str>,
+    error_envelope: Option<&str>,
+    registry: Option<&Registry>,
+) -> Option<()> {
+    let Some(Value::Object(responses)) = operation.get_mut("responses") else {
+        return Some(());
+    };
+    for (code, response) in responses.iter_mut() {
+        if code.starts_with('2') {
+            let Some(envelope) = envelope else {
+                continue;
+            };
+            let Some(media) = response
+                .get_mut("content")
+                .and_then(|c| c.get_mut("application/json"))
+            else {
+                continue;
+            };
+            match envelope_argument(&media["schema"], registry) {
+                Some(inner) if !inner.starts_with(&format!("${}<", envelope)) => {
+                    media["schema"] = json!({ "$ref": format!("${}<{}>", envelope, inner) });
+                }
+                Some(_) => {}
+                None => match inline_envelope(envelope, &media["schema"], registry) {
+                    Some(wrapped) => media["schema"] = wrapped,
+                    None => {
+                        log::error!(
+                            "Response {} cannot be wrapped in '{}': the envelope must be an `@openapi<T>` blueprint with one parameter. Skipping route.",
+                            code,
+                            envelope
+                        );
+                        return None;
+                    }
+                },
+            }
+        } else if code.starts_with('4') || code.starts_with('5') || code == "default" {
+            let Some(error_envelope) = error_envelope else {
+                continue;
+            };
+            if response.get("content").is_none() {
+                response["content"] = json!({
+                    "application/json": { "schema": { "$ref": format!("${}", error_envelope) } }
+                });
+            }
+        }
+    }
+    Some(())
+}
+
+/// Inline instance of the `envelope` blueprint for a body that is not a single
+/// component (`Vec<User>`, primitives, inline objects): every `$ref: $T` in the
+/// blueprint becomes the body schema itself.
+fn inline_envelope(envelope: &str, schema: &Value, registry: Option<&Registry>) -> Option<Value> {
+    let blueprint = registry?.blueprints.get(envelope)?;
+    let [param] = blueprint.params.as_slice() else {
+        return None;
+    };
+    let mut instance = serde_yaml_ng::from_str::<Value>(&blueprint.body).ok()?;
+    let target = json!({ "$ref": format!("${}", param) });
+
+    fn substitute(node: &mut Value, target: &Value, schema: &Value) -> bool {
+        if node == target {
+            *node = schema.clone();
+            return true;
+        }
+        match node {
+            Value::Object(map) => map
+                .values_mut()
+                .fold(false, |found, v| substitute(v, target, schema) | found),
+            Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |found, v| substitute(v, target, schema) | found),
+            _ => false,
+        }
+    }
+    substitute(&mut instance, &target, schema).then_some(instance)
+}
+
+/// Blueprint argument (`$User`, `$Page<User>`) for a response schema that is a
+/// single component reference. Arrays, primitives and inline schemas go through
+/// [`inline_envelope`] instead.
+fn envelope_argument(schema: &Value, registry: Option<&Registry>) -> Option<String> {
+    let object = schema.as_object().filter(|o| o.len() == 1)?;
+    let reference = object.get("$ref")?.as_str()?;
+    let is_ident = |name: &str| {
+        name.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+
+    if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+        return is_ident(name).then(|| format!("${}", name));
+    }
+    let name = reference.strip_prefix('$')?;
+    match name.split_once('<') {
+        None => is_ident(name).then(|| reference.to_string()),
+        // Unresolved generic (`$Page<User>`): only blueprints can be instantiated
+        Some((blueprint, _)) => {
+            let known = registry.is_none_or(|r| r.blueprints.contains_key(blueprint));
+            (is_ident(blueprint) && name.ends_with('>') && known).then(|| reference.to_string())
+        }
+    }
+}
+
+// `@stream 200: ...` -> ` 200: ...`; rejects longer directives (`@streamfoo`).
+fn directive<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    line.strip_prefix(name)
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+const SSE_MIME: &str = "text/event-stream";
+const NDJSON_MIME: &str = "application/x-ndjson";
+
+/// Schema of each streamed item; renamed to `itemSchema` for OpenAPI 3.2 documents
+/// (see `merger::merge_openapi`).
+pub const ITEM_SCHEMA_KEY: &str = "x-item-schema";
+
+/// `<code>: [Type] ["desc"] [sse|ndjson|<mime>]` -> streaming response with an item schema.
+fn parse_stream(rest: &str) -> Option<(String, Value)> {
+    let Some((code, residue)) = rest.split_once(':') else {
+        log::error!(
+            "Malformed @stream '{}': expected '<code>: [Type] [\"desc\"] sse|ndjson'",
+            rest
+        );
+        return None;
+    };
+    let residue = residue.trim();
+    let (type_str, residue) = if residue.starts_with('"') {
+        ("", residue)
+    } else {
+        split_type_token(residue)
+    };
+    let (desc, format) = match residue.strip_prefix('"') {
+        Some(quoted) => match quoted.find('"') {
+            Some(end) => (&quoted[..end], quoted[end + 1..].trim()),
+            None => (quoted, ""),
+        },
+        None => ("", residue),
+    };
+
+    let item = (!type_str.is_empty()).then(|| return_schema(type_str));
+    let (mime, item_schema) = match format {
+        "" | "sse" => (SSE_MIME, sse_event(None, item)),
+        "ndjson" => (NDJSON_MIME, item.unwrap_or_else(|| json!({}))),
+        mime if mime.contains('/') => (mime, item.unwrap_or_else(|| json!({}))),
+        other => {
+            log::error!(
+                "Unknown @stream format '{}': expected sse, ndjson or a media type",
+                other
+            );
+            return None;
+        }
+    };
+
+    Some((
+        normalize_status(code),
+        json!({
+            "description": desc,
+            "content": { mime: { "schema": { "type": "string" }, ITEM_SCHEMA_KEY: item_schema } }
+        }),
+    ))
+}
+
+/// `<code> <event>: [Type] ["desc"]` -> `(code, event schema)`.
+fn parse_stream_event(rest: &str) -> Option<(String, Value)> {
+    let (code, spec) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let Some((name, residue)) = spec.split_once(':') else {
+        log::error!(
+            "Malformed @stream-event '{}': expected '<code> <event>: [Type] [\"desc\"]'",
+            rest
+        );
+        return None;
+    };
+    let residue = residue.trim();
+    let (type_str, desc) = if residue.starts_with('"') {
+        ("", residue)
+    } else {
+        split_type_token(residue)
+    };
+    let payload = (!type_str.is_empty()).then(|| return_schema(type_str));
+    let mut event = sse_event(Some(name.trim()), payload);
+    let desc = desc.trim().trim_matches('"');
+    if !desc.is_empty() {
+        event["description"] = json!(desc);
+    }
+    Some((normalize_status(code), event))
+}
+
+// One Server-Sent Event: JSON `data` payload, optionally a fixed `event` name.
+fn sse_event(name: Option<&str>, payload: Option<Value>) -> Value {
+    let mut data = json!({ "type": "string" });
+    if let Some(payload) = payload {
+        data["contentMediaType"] = json!("application/json");
+        data["contentSchema"] = payload;
+    }
+    let event = match name {
+        Some(name) => json!({ "type": "string", "enum": [name] }),
+        None => json!({ "type": "string" }),
+    };
+    let mut required = vec!["data"];
+    if name.is_some() {
+        required.insert(0, "event");
+    }
+    json!({
+        "type": "object",
+        "required": required,
+        "properties": {
+            "event": event,
+            "data": data,
+            "id": { "type": "string" },
+            "retry": { "type": "integer", "minimum": 0 }
+        }
+    })
+}
+
+const PROBLEM_REF: &str = "#/components/schemas/Problem";
+
+/// `<code> [$Extension] ["desc"]` -> `application/problem+json` response referencing
+/// the built-in `Problem` schema (composed with the extension via `allOf`).
+fn parse_problem(rest: &str) -> Option<(String, Value)> {
+    let (code, residue) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let code = code.trim_end_matches(':');
+    if code.is_empty() {
+        log::error!("Malformed @problem: expected '<code> [$Extension] [\"desc\"]'");
+        return None;
+    }
+    let residue = residue.trim();
+    let (extension, residue) = if residue.starts_with('"') {
+        ("", residue)
+    } else {
+        split_type_token(residue)
+    };
+
+    let base = json!({ "$ref": PROBLEM_REF });
+    let schema = if extension.is_empty() {
+        base
+    } else {
+        json!({ "allOf": [base, type_schema(extension)] })
+    };
+    let desc = residue.trim().trim_matches('"');
+    Some((
+        normalize_status(code),
+        json!({
+            "description": desc,
+            "content": { "application/problem+json": { "schema": schema } }
+        }),
+    ))
+}
+
+/// Built-in RFC 9457 problem details schema.
+pub fn problem_schema() -> Value {
+    json!({
+        "type": "object",
+        "description": "Problem details (RFC 9457)",
+        "properties": {
+            "type": {
+                "type": "string",
+                "format": "uri-reference",
+                "default": "about:blank",
+                "description": "URI reference identifying the problem type"
+            },
+            "title": {
+                "type": "string",
+                "description": "Short, human-readable summary of the problem type"
+            },
+            "status": {
+                "type": "integer",
+                "format": "int32",
+                "minimum": 100,
+                "maximum": 599,
+                "description": "HTTP status code"
+            },
+            "detail": {
+                "type": "string",
+                "description": "Explanation specific to this occurrence"
+            },
+            "instance": {
+                "type": "string",
+                "format": "uri-reference",
+                "description": "URI reference identifying this occurrence"
+            }
+        },
+        "additionalProperties": true
+    })
+}
+
+// Ships the built-in `Problem` schema with operations using it, unless the
+// project defines its own.
+fn add_problem_component(item: &mut Value, ctx: &RouteContext) {
+    if !item.to_string().contains(PROBLEM_REF) {
+        return;
+    }
+    if ctx
+        .registry
+        .is_some_and(|r| r.schemas.contains_key("Problem"))
+    {
+        return;
+    }
+    item["components"]["schemas"]["Problem"] = problem_schema();
+}
+
+// `scheme("scope", ...)` -> `{scheme: [scopes]}`
+fn parse_security(rest: &str) -> Value {
+    let (scheme, scopes) = if let Some(paren_start) = rest.find('(') {
+        let name = rest[..paren_start].trim();
+        let inner = &rest[paren_start + 1..rest.len() - 1];
+        let s: Vec<String> = inner
+            .split(',')
+            .map(|s| s.trim().trim_matches('"').to_string())
+            .collect();
+        (name, s)
+    } else {
+        (rest, vec![])
+    };
+    json!({ scheme: scopes })
+}
+
+/// Applies `@default-*` lines (outer scopes first) where the route declares
+/// nothing itself: tags without `@tag`, security without `@security`, and
+/// responses / header params by status code and name. Tags and security of an
+/// inner scope replace the outer ones.
+fn apply_defaults(operation: &mut Value, defaults: &[&str]) {
+    let mut tags: Vec<String> = Vec::new();
+    // `Some(vec![])`: the innermost scope declared `@default-security none`
+    let mut security: Option<Vec<Value>> = None;
+    let mut responses = serde_json::Map::new();
+    let mut headers: Vec<(&str, Value)> = Vec::new();
+    // Scope that set `tags` / `security`: a new scope replaces them
+    let (mut scope, mut tags_scope, mut security_scope) = (0, 0, 0);
+
+    for line in defaults {
+        let (kind, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match kind {
+            "scope" => scope += 1,
+            "tag" => {
+                if tags_scope != scope {
+                    tags.clear();
+                    tags_scope = scope;
+                }
+                for tag in rest.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                    if !tags.iter().any(|t| t == tag) {
+                        tags.push(tag.to_string());
+                    }
+                }
+            }
+            "security" => {
+                if security_scope != scope || security.is_none() {
+                    security = Some(Vec::new());
+                    security_scope = scope;
+                }
+                // `@default-security none` makes the scope public again
+                if rest != "none" {
+                    security.get_or_insert_default().push(parse_security(rest));
+                }
+            }
+            "problem" => {
+                if let Some((code, response)) = parse_problem(rest) {
+                    responses.insert(code, response);
+                }
+            }
+            "response" => {
+                // `@default-response 401 $Problem` (the `@return` colon is optional)
+                let (code, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let spec = format!("{}: {}", code.trim_end_matches(':'), tail.trim());
+                if let Some((code, response)) = parse_return(&spec) {
+                    responses.insert(code, response);
+                }
+            }
+            "header-param" => {
+                if let Some((name, spec)) = parse_param_spec(rest) {
+                    headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+                    headers.push((name, spec));
+                }
+            }
+            _ => log::warn!("Unknown scoped directive '@default-{}'", kind),
+        }
+    }
+
+    if !tags.is_empty() && operation["tags"].as_array().is_none_or(|t| t.is_empty()) {
+        operation["tags"] = json!(tags);
+    }
+    // An empty list overrides a root-level `security`
+    if let Some(security) = security {
+        if operation.get("security").is_none() {
+            operation["security"] = json!(security);
+        }
+    }
+    for (code, response) in responses {
+        if operation["responses"].get(&code).is_none() {
+            operation["responses"][code] = response;
+        }
+    }
+    for (name, spec) in headers {
+        if !has_param(operation, "header", name) {
+            let mut param = json!({ "name": name, "in": "header" });
+            json_merge(&mut param, spec);
+            push_param(operation, param);
+        }
+    }
+}
+
+/// `(method, path)` of every `@route` line, with `GET|HEAD` expanded.
+fn expand_routes(doc_lines: &[String]) -> Vec<(String, String)> {
+    let mut routes = Vec::new();
+    for line in doc_lines {
+        let Some(rest) = line.trim().strip_prefix("@route ") else {
+            continue;
+        };
+        let Some((methods, path)) = rest.trim().split_once(char::is_whitespace) else {
+            continue;
+        };
+        for method in methods.split('|').filter(|m| !m.is_empty()) {
+            routes.push((method.to_string(), path.trim().to_string()));
+        }
+    }
+    routes
+}
+
+/// Compiles one operation per route and merges them into a single `paths` map.
+/// The first operation keeps the operationId, the others get `{id}_{method}`
+/// (plus `_{n}` when that is still taken).
+fn parse_multi_route(
+    doc_lines: &[String],
+    operation_id: &str,
+    routes: &[(String, String)],
+    ctx: &RouteContext,
+) -> Option<String> {
+    let explicit_id = doc_lines
+        .iter()
+        .find_map(|l| l.trim().strip_prefix("@operation-id"))
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+    let base_id = explicit_id.unwrap_or(operation_id);
+    let shared: Vec<String> = doc_lines
+        .iter()
+        .filter(|l| {
+            let trimmed = l.trim();
+            !trimmed.starts_with("@route ") && !trimmed.starts_with("@operation-id")
+        })
+        .cloned()
+        .collect();
+
+    let mut used_ids: Vec<String> = Vec::new();
+    let mut paths = serde_json::Map::new();
+    let mut extra = json!({});
+    for (idx, (method, path)) in routes.iter().enumerate() {
+        let mut id = if idx == 0 {
+            base_id.to_string()
+        } else {
+            format!("{}_{}", base_id, method.to_lowercase())
+        };
+        if used_ids.contains(&id) {
+            id = format!("{}_{}", id, idx + 1);
+        }
+        used_ids.push(id.clone());
+
+        let mut lines = vec![format!("@route {} {}", method, path)];
+        if explicit_id.is_some() {
+            lines.push(format!("@operation-id {}", id));
+        }
+        lines.extend(shared.iter().cloned());
+        let Some(yaml) = parse_route_dsl_with(&lines, &id, ctx) else {
+            continue;
+        };
+        let Ok(Value::Object(mut generated)) = serde_yaml_ng::from_str::<Value>(&yaml) else {
+            continue;
+        };
+        if let Some(Value::Object(generated_paths)) = generated.remove("paths") {
+            for (path, item) in generated_paths {
+                json_merge(paths.entry(path).or_insert_with(|| json!({})), item);
+            }
+        }
+        // Built-in components (e.g. `Problem`)
+        json_merge(&mut extra, Value::Object(generated));
+    }
+
+    if paths.is_empty() {
+        return None;
+    }
+    json_merge(&mut extra, json!({ "paths": paths }));
+    serde_yaml_ng::to_string(&extra)
+        .ok()
+        .map(|generated| generated.trim_start_matches("---\n").to_string())
+}
+
+/// Whether a doc line starts an operation (`@route` or `@webhook`).
+pub fn is_operation_line(line: &str) -> bool {
+    matches!(line.split_whitespace().next(), Some("@route" | "@webhook"))
+}
+
+/// Parses `<name> <expression> <METHOD> [$Payload] ["summary"]` of `@callback`
+/// into the callback name, runtime expression, method and operation.
+fn parse_callback(rest: &str) -> Option<(String, String, String, Value)> {
+    let mut parts = rest.splitn(4, char::is_whitespace);
+    let (Some(name), Some(expression), Some(verb)) = (parts.next(), parts.next(), parts.next())
+    else {
+        log::error!(
+            "Malformed @callback '{}': expected '<name> <expression> <METHOD> [$Payload]'",
+            rest
+        );
+        return None;
+    };
+    let tail = parts.next().unwrap_or("").trim();
+
+    let mut callback_op = json!({ "responses": { "200": { "description": "OK" } } });
+    let (payload, summary) = if tail.starts_with('"') {
+        ("", tail)
+    } else {
+        split_type_token(tail)
+    };
+    if !payload.is_empty() {
+        callback_op["requestBody"] = json!({
+            "content": { "application/json": { "schema": type_schema(payload) } }
+        });
+    }
+    let summary = summary.trim().trim_matches('"');
+    if !summary.is_empty() {
+        callback_op["summary"] = json!(summary);
+    }
+    Some((
+        name.to_string(),
+        expression.to_string(),
+        verb.to_lowercase(),
+        callback_op,
+    ))
+}
+
+/// Parses `name: Type attrs... "desc"` (shared by the parameter directives and
+/// `@response-header`) into the name and `schema` / `required` / `deprecated` /
+/// `example` / `description` fields.
+fn parse_param_spec(rest: &str) -> Option<(&str, Value)> {
+    let colon_idx = rest.find(':')?;
+    let name = rest[..colon_idx].trim();
+    let type_part = rest[colon_idx + 1..].trim();
+
+    let tokens_vec = split_param_tokens(type_part);
+    let first = tokens_vec.first().copied().unwrap_or("");
+
+    let (type_def, start_idx) = if is_param_attribute(first) || first.starts_with('"') {
+        ("String", 0)
+    } else if !tokens_vec.is_empty() {
+        (first, 1)
+    } else {
+        ("String", 0)
+    };
+
+    let (mut schema, mut is_required) = if let Ok(ty) = syn::parse_str::<syn::Type>(type_def) {
+        map_syn_type_to_openapi(&ty)
+    } else {
+        (json!({ "type": "string" }), true)
+    };
+
+    let mut spec = json!({});
+    let mut content: Option<&str> = None;
+    let mut desc_tokens = Vec::new();
+    let mut in_desc = false;
+
+    // Attributes check in tokens
+    for token in tokens_vec.iter().skip(start_idx) {
+        if in_desc {
+            desc_tokens.push(*token);
+            continue;
+        }
+
+        match *token {
+            "deprecated" => spec["deprecated"] = json!(true),
+            "required" => is_required = true,
+            "allow-empty" => spec["allowEmptyValue"] = json!(true),
+            "allow-reserved" => spec["allowReserved"] = json!(true),
+            t if t.starts_with('"') => {
+                in_desc = true;
+                desc_tokens.push(t);
+            }
+            t => {
+                let Some((key, raw)) = t.split_once('=') else {
+                    log::error!("Unknown attribute '{}' on parameter '{}'", t, name);
+                    continue;
+                };
+                let raw = raw.trim_matches('"');
+                if let Err(message) =
+                    apply_param_attribute(&mut spec, &mut schema, &mut content, key, raw)
+                {
+                    log::error!("Invalid '{}' on parameter '{}': {}", t, name, message);
+                }
+            }
+        }
+    }
+
+    spec["required"] = json!(is_required);
+    match content {
+        // `content=` replaces `schema` (complex values serialized as a media type)
+        Some(mime) => spec["content"] = json!({ mime: { "schema": schema } }),
+        None => spec["schema"] = schema,
+    }
+    if !desc_tokens.is_empty() {
+        spec["description"] = json!(desc_tokens.join(" ").trim_matches('"'));
+    }
+    Some((name, spec))
+}
+
+const PARAM_ATTRIBUTES: &[&str] = &[
+    "example",
+    "min",
+    "max",
+    "min-length",
+    "max-length",
+    "pattern",
+    "default",
+    "enum",
+    "style",
+    "explode",
+    "content",
+];
+
+// `required`, `allow-empty`, `key=value`, ... (as opposed to a type)
+fn is_param_attribute(token: &str) -> bool {
+    matches!(
+        token,
+        "deprecated" | "required" | "allow-empty" | "allow-reserved"
+    ) || token
+        .split_once('=')
+        .is_some_and(|(key, _)| PARAM_ATTRIBUTES.contains(&key))
+}
+
+// Applies a `key=value` token; values are typed according to the schema.
+fn apply_param_attribute<'a>(
+    spec: &mut Value,
+    schema: &mut Value,
+    content: &mut Option<&'a str>,
+    key: &str,
+    raw: &'a str,
+) -> Result<(), String> {
+    if raw.is_empty() {
+        return Err("missing value".to_string());
+    }
+    let is_array = schema["type"] == "array";
+    match key {
+        "example" => spec["example"] = typed_value(schema, raw)?,
+        "default" => schema["default"] = typed_value(schema, raw)?,
+        "enum" => {
+            let target = if is_array {
+                &mut schema["items"]
+            } else {
+                schema
+            };
+            let values = raw
+                .split('|')
+                .map(|v| typed_value(target, v))
+                .collect::<Result<Vec<_>, _>>()?;
+            target["enum"] = json!(values);
+        }
+        "min" | "max" => {
+            let value = typed_value(&json!({ "type": "number" }), raw)?;
+            let keyword = if key == "min" { "minimum" } else { "maximum" };
+            schema[keyword] = if schema["type"] == "integer" {
+                json!(
+                    raw.parse::<i64>()
+                        .map_err(|_| format!("expected an integer, got '{}'", raw))?
+                )
+            } else {
+                value
+            };
+        }
+        "min-length" | "max-length" => {
+            let length: u64 = raw
+                .parse()
+                .map_err(|_| format!("expected a non-negative integer, got '{}'", raw))?;
+            let keyword = match (key, is_array) {
+                ("min-length", true) => "minItems",
+                ("max-length", true) => "maxItems",
+                ("min-length", false) => "minLength",
+                _ => "maxLength",
+            };
+            schema[keyword] = json!(length);
+        }
+        "pattern" => {
+            let target = if is_array {
+                &mut schema["items"]
+            } else {
+                schema
+            };
+            target["pattern"] = json!(raw);
+        }
+        "style" => {
+            const STYLES: &[&str] = &[
+                "form",
+                "simple",
+                "label",
+                "matrix",
+                "spaceDelimited",
+                "pipeDelimited",
+                "deepObject",
+            ];
+            if !STYLES.contains(&raw) {
+                return Err(format!("expected one of {}", STYLES.join(", ")));
+            }
+            spec["style"] = json!(raw);
+        }
+        "explode" => {
+            let explode: bool = raw
+                .parse()
+                .map_err(|_| format!("expected true or false, got '{}'", raw))?;
+            spec["explode"] = json!(explode);
+        }
+        "content" => {
+            if !raw.contains('/') {
+                return Err(format!("expected a media type, got '{}'", raw));
+            }
+            *content = Some(raw);
+        }
+        _ => return Err("unknown attribute".to_string()),
+    }
+    Ok(())
+}
+
+// Parses `raw` as a value of `schema` (`Vec<T>` values are comma separated).
+fn typed_value(schema: &Value, raw: &str) -> Result<Value, String> {
+    match schema["type"].as_str() {
+        Some("integer") => raw
+            .parse::<i64>()
+            .map(|v| json!(v))
+            .map_err(|_| format!("expected an integer, got '{}'", raw)),
+        Some("number") => raw
+            .parse::<f64>()
+            .map(|v| json!(v))
+            .map_err(|_| format!("expected a number, got '{}'", raw)),
+        Some("boolean") => raw
+            .parse::<bool>()
+            .map(|v| json!(v))
+            .map_err(|_| format!("expected true or false, got '{}'", raw)),
+        Some("array") => raw
+            .split(',')
+            .filter(|v| !v.is_empty())
+            .map(|v| typed_value(&schema["items"], v.trim()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        _ => Ok(json!(raw)),
+    }
+}
+
+// Splits on whitespace, keeping quoted sections (`pattern="^[a-z ]+$"`, `"A description"`) together.
+fn split_param_tokens(s: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    for (idx, c) in s.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        if c.is_whitespace() && !in_quotes {
+            if let Some(begin) = start.take() {
+                tokens.push(&s[begin..idx]);
+            }
+        } else if start.is_none() {
+            start = Some(idx);
+        }
+    }
+    if let Some(begin) = start {
+        tokens.push(&s[begin..]);
+    }
+    tokens
+}
+
+/// Parses `<code> <Name> key=value...` of `@link` into an OpenAPI Link object.
+/// `operationId`, `operationRef`, `requestBody` and `description` are link
+/// fields, every other pair is a link parameter.
+fn parse_link(rest: &str) -> Option<(String, String, Value)> {
+    let mut tokens = rest.split_whitespace();
+    let (Some(code), Some(name)) = (tokens.next(), tokens.next()) else {
+        log::warn!(
+            "Malformed @link '{}': expected '<code> <Name> key=value...'",
+            rest
+        );
+        return None;
+    };
+
+    let mut link = json!({});
+    for token in tokens {
+        let Some((key, value)) = token.split_once('=') else {
+            log::warn!("Ignoring @link token '{}' (expected key=value)", token);
+            continue;
+        };
+        let value = value.trim_matches('"');
+        match key {
+            "operationId" | "operationRef" | "requestBody" | "description" => {
+                link[key] = json!(value)
+            }
+            _ => link["parameters"][key] = json!(value),
+        }
+    }
+    if link.get("operationId").is_none() && link.get("operationRef").is_none() {
+        log::warn!("@link '{}' needs operationId= or operationRef=", name);
+        return None;
+    }
+    Some((normalize_status(code), name.to_string(), link))
+}
+
+// `4xx` -> `4XX`, `Default` -> `default`
+fn normalize_status(code: &str) -> String {
+    let code = code.trim().trim_end_matches(':');
+    if code.eq_ignore_ascii_case("default") {
+        "default".to_string()
+    } else {
+        code.to_uppercase()
+    }
+}
+
+/// Parses the part after `@return` into a status code and a response object.
+/// The code accepts exact statuses, ranges (`4XX`) and `default`.
+fn parse_return(rest: &str) -> Option<(String, Value)> {
+    let (code, residue) = rest.split_once(':')?;
+    let code = normalize_status(code);
+    let residue = residue.trim();
+
+    // Type token (absent for `@return 204: "Deleted"`)
+    let (type_str, residue) = if residue.starts_with('"') {
+        ("()", residue)
+    } else {
+        split_type_token(residue)
+    };
+
+    let (desc, residue) = match residue.strip_prefix('"') {
+        Some(quoted) => match quoted.find('"') {
+            Some(end) => (Some(&quoted[..end]), quoted[end + 1..].trim()),
+            None => (Some(quoted), ""),
+        },
+        None => (None, residue),
+    };
+
+    let mut resp_obj = json!({ "description": desc.unwrap_or_default() });
+    if type_str == "()" || type_str == "unit" {
+        return Some((code, resp_obj));
+    }
+
+    let is_binary = type_str == "file" || type_str == "binary";
+    let schema = if is_binary {
+        json!({ "type": "string", "format": "binary" })
+    } else {
+        return_schema(type_str)
+    };
+
+    let mut mimes: Vec<&str> = residue
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|m| !m.is_empty())
+        .collect();
+    if mimes.is_empty() {
+        mimes.push(if is_binary {
+            "application/octet-stream"
+        } else {
+            "application/json"
+        });
+    }
+
+    let mut content = serde_json::Map::new();
+    for mime in mimes {
+        content.insert(mime.to_string(), json!({ "schema": schema }));
+    }
+    resp_obj["content"] = Value::Object(content);
+    Some((code, resp_obj))
+}
+
+// Splits a leading type off `residue`; whitespace inside `<>`, `()`, `[]`, `{}` is kept.
+fn split_type_token(residue: &str) -> (&str, &str) {
+    let mut depth = 0i32;
+    for (idx, c) in residue.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            c if c.is_whitespace() && depth <= 0 => {
+                return (&residue[..idx], residue[idx..].trim_start());
+            }
+            _ => {}
+        }
+    }
+    (residue, "")
+}
+
+fn return_schema(type_str: &str) -> Value {
+    if type_str.starts_with('{') {
+        serde_yaml_ng::from_str(type_str).unwrap_or_else(|_| json!({ "$ref": type_str }))
+    } else if type_str == "String" || type_str == "str" {
+        json!({ "type": "string" })
+    } else {
+        type_schema(type_str)
+    }
+}
+
+/// Adds a response, merging its content types into an existing response
+/// with the same status. A non-empty existing description is kept.
+fn merge_response(operation: &mut Value, code: &str, response: Value) {
+    let responses = operation.get_mut("responses").unwrap();
+    let Some(existing) = responses.get_mut(code) else {
+        responses[code] = response;
+        return;
+    };
+    if existing["description"].as_str().is_none_or(str::is_empty) {
+        existing["description"] = response["description"].clone();
+    }
+    if let Some(Value::Object(content)) = response.get("content") {
+        if existing.get("content").is_none() {
+            existing["content"] = json!({});
+        }
+        if let Some(Value::Object(target)) = existing.get_mut("content") {
+            for (mime, media) in content {
+                target.insert(mime.clone(), media.clone());
+            }
+        }
+    }
+    for key in ["headers", "links"] {
+        if let Some(Value::Object(extra)) = response.get(key) {
+            if existing.get(key).is_none() {
+                existing[key] = json!({});
+            }
+            if let Some(Value::Object(target)) = existing.get_mut(key) {
+                target.extend(extra.clone());
+            }
+        }
+    }
+}
+
+fn is_form_mime(mime: &str) -> bool {
+    mime.starts_with("multipart/") || mime == "application/x-www-form-urlencoded"
+}
+
+/// Inline object schema for form bodies: each struct field becomes a part,
+/// byte buffers (`Vec<u8>`, `Bytes`) and `@openapi file` fields are binary.
+/// Falls back to a reference when the struct is not indexed.
+fn form_schema(schema_ref: &str, mime: &str, ctx: &RouteContext) -> Value {
+    let Some(fields) = struct_fields(ctx, schema_ref) else {
+        return type_schema(schema_ref);
+    };
+
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for field in fields {
+        let name = field["name"].as_str().unwrap_or_default().to_string();
+        let mut schema = field["schema"].clone();
+        if mime.starts_with("multipart/") {
+            if schema["items"]["format"] == "uint8" {
+                schema = json!({ "type": "string", "format": "binary" });
+            }
+        }
+        for key in ["description", "deprecated"] {
+            if let Some(v) = field.get(key) {
+                schema[key] = v.clone();
+            }
+        }
+        if field["required"] == true {
+            required.push(json!(name));
+        }
+        properties.insert(name, schema);
+    }
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// Parses `<part> key=value...` or `<part> header Name: Type "desc"` of `@encoding`
+/// into an OpenAPI Encoding object fragment.
+fn parse_encoding(rest: &str) -> Option<(String, Value)> {
+    let Some((part, spec)) = rest.split_once(char::is_whitespace) else {
+        log::warn!(
+            "Malformed @encoding '{}': expected '<part> key=value...'",
+            rest
+        );
+        return None;
+    };
+    let spec = spec.trim();
+
+    if let Some(header) = spec.strip_prefix("header ") {
+        let (name, header) = parse_param_spec(header.trim())?;
+        return Some((part.to_string(), json!({ "headers": { name: header } })));
+    }
+
+    let mut encoding = json!({});
+    for token in spec.split_whitespace() {
+        let Some((key, value)) = token.split_once('=') else {
+            log::warn!("Ignoring @encoding token '{}' (expected key=value)", token);
+            continue;
+        };
+        let value = value.trim_matches('"');
+        match key {
+            "contentType" => encoding[key] = json!(value.split(',').collect::<Vec<_>>().join(", ")),
+            "style" => encoding[key] = json!(value),
+            "explode" | "allowReserved" => encoding[key] = json!(value != "false"),
+            _ => log::warn!("Unknown @encoding key '{}'", key),
+        }
+    }
+    Some((part.to_string(), encoding))
+}
+
+// Schema for a `@body`-style type reference.
+fn type_schema(schema_ref: &str) -> Value {
+    let is_std_generic = schema_ref.starts_with("Option<")
+        || schema_ref.starts_with("Vec<")
+        || schema_ref.starts_with("Box<")
+        || schema_ref.starts_with("Arc<")
+        || schema_ref.starts_with("Rc<")
+        || schema_ref.starts_with("Cow<")
+        || schema_ref.starts_with("HashMap<")
+        || schema_ref.starts_with("BTreeMap<");
+
+    if !is_std_generic
+        && (schema_ref.contains('<') || (schema_ref.starts_with('$') && schema_ref.contains('<')))
+    {
+        json!({ "$ref": schema_ref })
+    } else if let Ok(ty) = syn::parse_str::<syn::Type>(schema_ref) {
+        map_syn_type_to_openapi(&ty).0
+    } else if let Some(stripped) = schema_ref.strip_prefix('$') {
+        json!({ "$ref": format!("#/components/schemas/{}", stripped) })
+    } else {
+        json!({ "$ref": format!("#/components/schemas/{}", schema_ref) })
+    }
+}
+
+/// Fills whatever the explicit DSL left open from the `@infer` lines emitted
+/// by `handlers::signature_lines`.
+fn apply_inferred(
+    operation: &mut Value,
+    path: &str,
+    declared_path_params: &mut HashSet<String>,
+    inferred: &[String],
+    ctx: &RouteContext,
+) {
+    let template_re = Regex::new(r"\{(\w+)\}").unwrap();
+    let vars: Vec<&str> = template_re
+        .captures_iter(path)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect();
+    let implicit = inferred.iter().any(|l| l == "implicit-route");
+
+    // 1. Path params: by argument name first, then by extractor position / field name
+    let mut path_schemas: Vec<(String, Value)> = Vec::new();
+    let mut add_path = |name: &str, param: Value| {
+        if vars.contains(&name) && !path_schemas.iter().any(|(n, _)| n == name) {
+            path_schemas.push((name.to_string(), param));
+        }
+    };
+    for line in inferred {
+        if let Some((name, ty)) = line
+            .strip_prefix("path-param ")
+            .and_then(|rest| rest.split_once(':'))
+        {
+            add_path(name.trim(), json!({ "schema": type_schema(ty.trim()) }));
+        }
+    }
+    for line in inferred {
+        let Some(ty) = line.strip_prefix("path ").map(str::trim) else {
+            continue;
+        };
+        if let Some(tuple) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            for (var, elem) in vars.iter().zip(split_top_level(tuple)) {
+                add_path(var, json!({ "schema": type_schema(&elem) }));
+            }
+        } else if let Some(fields) = struct_fields(ctx, ty) {
+            for field in fields {
+                let name = field["name"].as_str().unwrap_or_default().to_string();
+                add_path(&name, field);
+            }
+        } else if let Some(var) = vars.first() {
+            let schema = type_schema(ty);
+            if schema.get("$ref").is_some() {
+                log::warn!(
+                    "Cannot expand path parameters of '{}': schema not found",
+                    ty
+                );
+            } else {
+                add_path(var, json!({ "schema": schema }));
+            }
+        }
+    }
+
+    for var in &vars {
+        if declared_path_params.contains(*var) {
+            continue;
+        }
+        let param = match path_schemas.iter().find(|(n, _)| n == var) {
+            Some((_, p)) => p.clone(),
+            None if implicit => json!({ "schema": { "type": "string" } }),
+            None => continue,
+        };
+        let mut param_obj = json!({ "name": var, "in": "path", "required": true });
+        for key in ["schema", "description", "deprecated"] {
+            if let Some(v) = param.get(key) {
+                param_obj[key] = v.clone();
+            }
+        }
+        declared_path_params.insert(var.to_string());
+        push_param(operation, param_obj);
+    }
+
+    for line in inferred {
+        if let Some(ty) = line.strip_prefix("query ") {
+            // 2. Query struct fields
+            expand_struct_params(operation, "query", ty.trim(), ctx);
+        } else if let Some(rest) = line.strip_prefix("header ") {
+            // 3. Typed headers
+            let mut parts = rest.split_whitespace();
+            let Some(name) = parts.next() else {
+                continue;
+            };
+            let optional = parts.next() == Some("optional");
+            if !has_param(operation, "header", name) {
+                push_param(
+                    operation,
+                    json!({
+                        "name": name,
+                        "in": "header",
+                        "required": !optional,
+                        "schema": { "type": "string" }
+                    }),
+                );
+            }
+        } else if let Some(rest) = line.strip_prefix("body ") {
+            // 4. Request body
+            let mut parts = rest.split_whitespace();
+            let (Some(ty), Some(mime)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if operation.get("requestBody").is_none() {
+                let schema = if ty == "Multipart" {
+                    json!({ "type": "object" })
+                } else if is_form_mime(mime) {
+                    form_schema(ty, mime, ctx)
+                } else {
+                    type_schema(ty)
+                };
+                operation["requestBody"] = json!({
+                    "content": { mime: { "schema": schema } }
+                });
+            }
+        } else if let Some(rest) = line.strip_prefix("return ") {
+            // 5. Default success response
+            let Some((code, ty)) = rest.split_once(':') else {
+                continue;
+            };
+            let responses = operation.get_mut("responses").unwrap();
+            let has_success = responses
+                .as_object()
+                .is_some_and(|r| r.keys().any(|k| k.starts_with('2')));
+            if !has_success {
+                responses[code.trim()] = json!({
+                    "description": "",
+                    "content": { "application/json": { "schema": type_schema(ty.trim()) } }
+                });
+            }
+        }
+    }
+}
+
+/// Adds one `location` parameter per property of the struct schema `name`,
+/// skipping names that are already declared.
+fn expand_struct_params(operation: &mut Value, location: &str, name: &str, ctx: &RouteContext) {
+    let Some(fields) = struct_fields(ctx, name) else {
+        log::warn!(
+            "Cannot expand {} parameters of '{}': schema not found",
+            location,
+            name
+        );
+        return;
+    };
+    for mut field in fields {
+        let field_name = field["name"].as_str().unwrap_or_default().to_string();
+        if has_param(operation, location, &field_name) {
+            continue;
+        }
+        field["in"] = json!(location);
+        // Nested objects are serialized as `filter[key]=value`
+        if location == "query" && is_object_schema(&field["schema"], ctx) {
+            field["style"] = json!("deepObject");
+            field["explode"] = json!(true);
+        }
+        push_param(operation, field);
+    }
+}
+
+fn is_object_schema(schema: &Value, ctx: &RouteContext) -> bool {
+    if schema["type"] == "object" {
+        return true;
+    }
+    schema["$ref"].as_str().is_some_and(|r| {
+        let name = r.rsplit('/').next().unwrap_or(r);
+        struct_fields(ctx, name).is_some()
+    })
+}
+
+fn push_param(operation: &mut Value, param: Value) {
+    if let Some(Value::Array(params)) = operation.get_mut("parameters") {
+        params.push(param);
+    }
+}
+
+fn has_param(operation: &Value, location: &str, name: &str) -> bool {
+    operation["parameters"].as_array().is_some_and(|params| {
+        params.iter().any(|p| {
+            p["in"] == location
+                && p["name"]
+                    .as_str()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+    })
+}
+
+// Splits `Uuid,u32` (tuple contents) on top-level commas.
+fn split_top_level(s: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in s.chars() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+/// Parameter objects (`name`, `required`, `schema`, `description`, `deprecated`)
+/// for the properties of an indexed struct schema.
+fn struct_fields(ctx: &RouteContext, name: &str) -> Option<Vec<Value>> {
+    let name = name.trim_start_matches('$');
+    let content = ctx.registry?.schemas.get(name)?;
+    let root: Value = serde_yaml_ng::from_str(content).ok()?;
+    let schema = root.get("components")?.get("schemas")?.get(name)?;
+
+    // Own properties plus inline parts of an inheritance composition
+    let mut parts = vec![schema];
+    if let Some(Value::Array(all_of)) = schema.get("allOf") {
+        parts.extend(all_of.iter());
+    }
+
+    let mut fields = Vec::new();
+    for part in parts {
+        let Some(Value::Object(props)) = part.get("properties") else {
+            continue;
+        };
+        let required: Vec<&str> = part
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        for (prop_name, prop) in props {
+            let mut schema = prop.clone();
+            let mut field = json!({
+                "name": prop_name,
+                "required": required.contains(&prop_name.as_str()),
+            });
+            if let Value::Object(map) = &mut schema {
+                if let Some(desc) = map.remove("description") {
+                    field["description"] = desc;
+                }
+                if let Some(deprecated) = map.remove("deprecated") {
+                    field["deprecated"] = deprecated;
+                }
+            }
+            field["schema"] = schema;
+            fields.push(field);
+        }
+    }
+    Some(fields)
+}
+
+// Splits `[name] path` of the example directives. The name defaults to the file stem.
+fn parse_example_ref(rest: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = rest.split_whitespace().collect();
+    match parts.as_slice() {
+        [file] => {
+            let stem = std::path::Path::new(file)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("example");
+            Some((stem.to_string(), file.to_string()))
+        }
+        [name, file, ..] => Some((name.to_string(), file.to_string())),
+        [] => {
+            log::warn!("Example directive without a file path: '{}'", rest);
+            None
+        }
+    }
+}
+
+fn example_placeholder(file: &str, line: Option<usize>) -> Value {
+    let mut placeholder = json!({ crate::visitor::EXAMPLE_FILE_MARKER: file });
+    if let Some(line) = line {
+        placeholder[crate::visitor::EXAMPLE_LINE_MARKER] = json!(line);
+    }
+    json!({ "value": placeholder })
+}
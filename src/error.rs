@@ -31,6 +31,34 @@ pub enum Error {
     #[error("Empty input: No files found in the specified directories.")]
     NoFilesFound,
 
+    #[error("Example file {example} referenced in {file}:{line} could not be loaded: {message}")]
+    ExampleFile {
+        file: PathBuf,
+        line: usize,
+        example: PathBuf,
+        message: String,
+    },
+
+    #[error(
+        "Duplicate operationId '{operation_id}': {first_file}:{first_line} and {second_file}:{second_line}"
+    )]
+    DuplicateOperationId {
+        operation_id: String,
+        first_file: PathBuf,
+        first_line: usize,
+        second_file: PathBuf,
+        second_line: usize,
+    },
+
+    #[error("Invalid route in {file}:{line}: the route DSL failed validation (see the log)")]
+    InvalidRoute { file: PathBuf, line: usize },
+
+    #[error("Links reference unknown operationIds: {}", .links.join("; "))]
+    DanglingLink { links: Vec<String> },
+
+    #[error("Sunset date passed for deprecated operations: {}", .operations.join("; "))]
+    SunsetPassed { operations: Vec<String> },
+
     #[error("YAML error in {file}:{line}: {source}\nContext:\n{context}")]
     SourceMapped {
         file: PathBuf,
@@ -0,0 +1,265 @@
+use serde_json::{Map, Value, json};
+
+/// Maximum nesting depth followed while synthesizing a single example.
+const MAX_DEPTH: usize = 16;
+
+/// Generates a deterministic example for every schema in `components/schemas`
+/// that has neither `example` nor `examples`.
+/// Returns a map of schema name -> example.
+pub fn synthesize_examples(spec: &serde_yaml_ng::Value) -> Map<String, Value> {
+    let schemas = spec
+        .get("components")
+        .and_then(|c| c.get("schemas"))
+        .and_then(|s| serde_json::to_value(s).ok())
+        .and_then(|s| match s {
+            Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let mut examples = Map::new();
+    for (name, schema) in &schemas {
+        if schema.get("example").is_some() || schema.get("examples").is_some() {
+            continue;
+        }
+        let mut visiting = vec![name.clone()];
+        let example = example_for(schema, &schemas, &mut visiting, 0);
+        if !example.is_null() {
+            examples.insert(name.clone(), example);
+        }
+    }
+    examples
+}
+
+/// Writes synthesized examples into the `example` keyword of their schemas.
+pub fn apply_examples(spec: &mut serde_yaml_ng::Value, examples: &Map<String, Value>) {
+    let Some(serde_yaml_ng::Value::Mapping(schemas)) = spec
+        .get_mut("components")
+        .and_then(|c| c.get_mut("schemas"))
+    else {
+        return;
+    };
+
+    for (name, example) in examples {
+        if let Some(serde_yaml_ng::Value::Mapping(schema)) = schemas.get_mut(name.as_str()) {
+            if let Ok(value) = serde_yaml_ng::to_value(example) {
+                schema.insert(serde_yaml_ng::Value::String("example".to_string()), value);
+            }
+        }
+    }
+}
+
+fn example_for(
+    schema: &Value,
+    schemas: &Map<String, Value>,
+    visiting: &mut Vec<String>,
+    depth: usize,
+) -> Value {
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+
+    // 1. Explicit values win
+    for key in ["example", "default", "const"] {
+        if let Some(v) = schema.get(key) {
+            return v.clone();
+        }
+    }
+    if let Some(first) = schema
+        .get("examples")
+        .and_then(|e| e.as_array())
+        .and_then(|e| e.first())
+    {
+        return first.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .and_then(|e| e.first())
+    {
+        return first.clone();
+    }
+
+    // 2. References (cycle guarded)
+    if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        let name = reference.trim_start_matches("#/components/schemas/");
+        if visiting.iter().any(|v| v == name) {
+            return Value::Null;
+        }
+        let Some(target) = schemas.get(name) else {
+            return Value::Null;
+        };
+        visiting.push(name.to_string());
+        let example = example_for(target, schemas, visiting, depth + 1);
+        visiting.pop();
+        return example;
+    }
+
+    // 3. Composition
+    if let Some(all_of) = schema.get("allOf").and_then(|a| a.as_array()) {
+        let mut merged = Map::new();
+        for part in all_of {
+            match example_for(part, schemas, visiting, depth + 1) {
+                Value::Object(obj) => merged.extend(obj),
+                Value::Null => {}
+                other => return other,
+            }
+        }
+        if let Value::Object(own) = object_example(schema, schemas, visiting, depth) {
+            merged.extend(own);
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema.get(key).and_then(|a| a.as_array()) {
+            for variant in variants {
+                let example = example_for(variant, schemas, visiting, depth + 1);
+                if !example.is_null() {
+                    return example;
+                }
+            }
+        }
+    }
+
+    // 4. Types
+    match schema_type(schema) {
+        Some("string") => string_example(schema),
+        Some("integer") => number_example(schema, true),
+        Some("number") => number_example(schema, false),
+        Some("boolean") => json!(true),
+        Some("array") => {
+            let item = schema
+                .get("items")
+                .map(|items| example_for(items, schemas, visiting, depth + 1))
+                .unwrap_or(Value::Null);
+            let max_items = schema.get("maxItems").and_then(|m| m.as_u64());
+            if item.is_null() || max_items == Some(0) {
+                return json!([]);
+            }
+            let min_items = schema.get("minItems").and_then(|m| m.as_u64()).unwrap_or(1);
+            let count = min_items.max(1).min(max_items.unwrap_or(u64::MAX));
+            Value::Array(vec![item; count as usize])
+        }
+        Some("object") | None => object_example(schema, schemas, visiting, depth),
+        _ => Value::Null,
+    }
+}
+
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => Some(t.as_str()),
+        // 3.1 type arrays: pick the first non-null type
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null"),
+        _ => None,
+    }
+}
+
+fn object_example(
+    schema: &Value,
+    schemas: &Map<String, Value>,
+    visiting: &mut Vec<String>,
+    depth: usize,
+) -> Value {
+    if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+        let mut obj = Map::new();
+        for (key, prop) in props {
+            let example = example_for(prop, schemas, visiting, depth + 1);
+            if !example.is_null() {
+                obj.insert(key.clone(), example);
+            }
+        }
+        return Value::Object(obj);
+    }
+    if let Some(additional) = schema.get("additionalProperties").filter(|a| a.is_object()) {
+        let example = example_for(additional, schemas, visiting, depth + 1);
+        if !example.is_null() {
+            return json!({ "key": example });
+        }
+    }
+    if schema_type(schema) == Some("object") {
+        return json!({});
+    }
+    Value::Null
+}
+
+fn string_example(schema: &Value) -> Value {
+    let format = schema.get("format").and_then(|f| f.as_str()).unwrap_or("");
+    let base = match format {
+        "date-time" => "2024-01-01T12:00:00Z",
+        "date" => "2024-01-01",
+        "time" => "12:00:00",
+        "duration" => "P1D",
+        "uuid" => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "email" => "user@example.com",
+        "uri" | "url" | "uri-reference" => "https://example.com",
+        "hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "byte" => "ZXhhbXBsZQ==",
+        "binary" => "",
+        "password" => "********",
+        "decimal" => "0.00",
+        "tel" => "+15555550100",
+        "objectid" => "507f1f77bcf86cd799439011",
+        _ => pattern_example(schema.get("pattern").and_then(|p| p.as_str())),
+    };
+
+    let mut value = base.to_string();
+    if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+        while (value.chars().count() as u64) < min {
+            value.push('x');
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+        value = value.chars().take(max as usize).collect();
+    }
+    json!(value)
+}
+
+// Covers the common single character-class patterns only.
+fn pattern_example(pattern: Option<&str>) -> &'static str {
+    let Some(pattern) = pattern else {
+        return "string";
+    };
+    let body = pattern.trim_start_matches('^').trim_end_matches('$');
+    let class = body.trim_end_matches(['+', '*']);
+    match class {
+        r"\d" | "[0-9]" => "123",
+        "[a-z]" => "abc",
+        "[A-Z]" => "ABC",
+        "[a-zA-Z]" | "[A-Za-z]" => "abc",
+        r"\w" | "[a-zA-Z0-9]" | "[A-Za-z0-9]" | "[a-z0-9]" => "abc123",
+        _ => "string",
+    }
+}
+
+fn number_example(schema: &Value, integer: bool) -> Value {
+    let minimum = schema.get("minimum").and_then(|m| m.as_f64()).or_else(|| {
+        schema
+            .get("exclusiveMinimum")
+            .and_then(|m| m.as_f64())
+            .map(|m| if integer { m.floor() + 1.0 } else { m + 1.0 })
+    });
+    let maximum = schema.get("maximum").and_then(|m| m.as_f64()).or_else(|| {
+        schema
+            .get("exclusiveMaximum")
+            .and_then(|m| m.as_f64())
+            .map(|m| if integer { m.ceil() - 1.0 } else { m - 1.0 })
+    });
+
+    let mut value = minimum.unwrap_or(0.0);
+    if let Some(max) = maximum {
+        if value > max {
+            value = max;
+        }
+    }
+
+    if integer {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
@@ -0,0 +1,411 @@
+use regex::Regex;
+use std::sync::OnceLock;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, FnArg, Lit, Pat, Signature, Token, Type};
+
+const METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace",
+];
+
+/// A route declared by a framework attribute macro
+/// (actix-web / rocket `#[get("/x")]`, `#[route(...)]`, poem-openapi `#[oai(...)]`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeRoute {
+    pub method: String,
+    pub path: String,
+}
+
+/// Collects the routes declared by framework attributes on a handler.
+pub fn route_attributes(attrs: &[Attribute]) -> Vec<AttributeRoute> {
+    let mut routes = Vec::new();
+
+    for attr in attrs {
+        let Some(name) = attr.path().segments.last().map(|s| s.ident.to_string()) else {
+            continue;
+        };
+        let is_method = METHODS.contains(&name.as_str());
+        if !is_method && name != "route" && name != "oai" {
+            continue;
+        }
+
+        let Ok(args) = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated) else {
+            continue;
+        };
+
+        let mut path = None;
+        let mut methods = Vec::new();
+        if is_method {
+            methods.push(name.clone());
+        }
+
+        for arg in &args {
+            match arg {
+                Expr::Lit(lit) => {
+                    if let (Lit::Str(s), None) = (&lit.lit, &path) {
+                        path = Some(s.value());
+                    }
+                }
+                // rocket: #[route(GET, uri = "/")]
+                Expr::Path(p) if !is_method => {
+                    if let Some(ident) = p.path.get_ident() {
+                        methods.push(ident.to_string().to_lowercase());
+                    }
+                }
+                Expr::Assign(assign) => {
+                    let (Expr::Path(key), Expr::Lit(value)) = (&*assign.left, &*assign.right)
+                    else {
+                        continue;
+                    };
+                    let Lit::Str(value) = &value.lit else {
+                        continue;
+                    };
+                    match key.path.get_ident().map(|i| i.to_string()).as_deref() {
+                        Some("path") | Some("uri") => path = Some(value.value()),
+                        Some("method") if !is_method => methods.push(value.value().to_lowercase()),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let Some(path) = path else {
+            continue;
+        };
+        for method in methods {
+            if METHODS.contains(&method.as_str()) {
+                routes.push(AttributeRoute {
+                    method,
+                    path: path.clone(),
+                });
+            } else {
+                log::warn!("Unsupported HTTP method '{}' on route '{}'", method, path);
+            }
+        }
+    }
+
+    routes
+}
+
+/// Builds the implicit DSL lines (`@route`, rocket query params) for a route
+/// declared by an attribute. Lines already present in `doc_lines` take precedence.
+pub fn implicit_route_lines(
+    route: &AttributeRoute,
+    sig: &Signature,
+    doc_lines: &[String],
+) -> Vec<String> {
+    let (path, query) = match route.path.split_once('?') {
+        Some((p, q)) => (p, Some(q)),
+        None => (route.path.as_str(), None),
+    };
+
+    let path = crate::router::normalize_path(&convert_path_syntax(path));
+    let mut lines = vec![format!("@route {} {}", route.method.to_uppercase(), path)];
+
+    // rocket: ?<page>&<size>
+    for segment in query.into_iter().flat_map(|q| q.split('&')) {
+        let Some(name) = segment
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .filter(|s| !s.ends_with(".."))
+        else {
+            continue;
+        };
+        if has_param_line(doc_lines, "@query-param", name) {
+            continue;
+        }
+        let ty = arg_type(sig, name).unwrap_or_else(|| "String".to_string());
+        lines.push(format!("@query-param {}: {}", name, ty));
+    }
+
+    lines
+}
+
+// rocket `<id>` / `<path..>` and actix `{id:\d+}` segments to `{id}`.
+fn convert_path_syntax(path: &str) -> String {
+    static ROCKET_RE: OnceLock<Regex> = OnceLock::new();
+    let rocket_re = ROCKET_RE.get_or_init(|| Regex::new(r"<(\w+)(?:\.\.)?>").unwrap());
+    static ACTIX_RE: OnceLock<Regex> = OnceLock::new();
+    let actix_re = ACTIX_RE.get_or_init(|| Regex::new(r"\{(\w+):[^}]*\}").unwrap());
+
+    let path = rocket_re.replace_all(path, "{$1}");
+    actix_re.replace_all(&path, "{$1}").into_owned()
+}
+
+/// Derives low-precedence `@infer` DSL lines from a handler signature:
+///
+/// - `Path<u32>` / `Path<(Uuid, u32)>` / `Path<Params>` -> path params
+/// - `Query<Filter>` -> query params from the struct fields
+/// - `Json<T>` / `Form<T>` / `Multipart` -> request body
+/// - `TypedHeader<T>` -> header param
+/// - `Json<T>`, `Result<Json<T>, E>`, `(StatusCode, Json<T>)` -> 200 response
+///
+/// Plain arguments (`id: u32`) are offered as path params matched by name.
+/// `implicit` marks routes without a written `@route`, whose remaining path
+/// params default to strings.
+pub fn signature_lines(sig: &Signature, implicit: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for input in &sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            continue;
+        };
+        let (ty, optional) = match generic_arg(&arg.ty, "Option") {
+            Some(inner) => (inner, true),
+            None => (&*arg.ty, false),
+        };
+        let Some(extractor) = last_ident(ty) else {
+            continue;
+        };
+
+        match extractor.as_str() {
+            "Path" => {
+                if let Some(inner) = generic_arg(ty, "Path").and_then(dsl_type) {
+                    lines.push(format!("@infer path {}", inner));
+                }
+            }
+            "Query" => {
+                if let Some(inner) = generic_arg(ty, "Query").and_then(dsl_type) {
+                    lines.push(format!("@infer query {}", inner));
+                }
+            }
+            "Json" | "Form" => {
+                let mime = if extractor == "Json" {
+                    "application/json"
+                } else {
+                    "application/x-www-form-urlencoded"
+                };
+                if let Some(inner) = generic_arg(ty, &extractor).and_then(dsl_type) {
+                    lines.push(format!("@infer body {} {}", inner, mime));
+                }
+            }
+            "Multipart" => lines.push("@infer body Multipart multipart/form-data".to_string()),
+            "TypedHeader" => {
+                if let Some(header) = generic_arg(ty, "TypedHeader").and_then(last_ident) {
+                    let flag = if optional { " optional" } else { "" };
+                    lines.push(format!("@infer header {}{}", header_name(&header), flag));
+                }
+            }
+            // Plain arguments (framework state and extractors excluded)
+            _ if !matches!(&*arg.ty, Type::Path(p)
+                if p.path.segments.iter().any(|s| !s.arguments.is_none())) =>
+            {
+                if let Pat::Ident(p) = &*arg.pat {
+                    if let Some(t) = dsl_type(&arg.ty) {
+                        lines.push(format!("@infer path-param {}: {}", p.ident, t));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        if let Some(body) = json_response(ty) {
+            lines.push(format!("@infer return 200: {}", body));
+        }
+        if let Some(error) = result_error(ty) {
+            lines.push(format!("@infer errors {}", error));
+        }
+    }
+
+    if implicit {
+        lines.push("@infer implicit-route".to_string());
+    }
+
+    lines
+}
+
+// `Json<T>`, `Result<Json<T>, E>`, `(StatusCode, Json<T>)`, `(StatusCode, [headers], Json<T>)`
+fn json_response(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Tuple(t) => t.elems.iter().rev().find_map(json_response),
+        Type::Paren(p) => json_response(&p.elem),
+        _ => match last_ident(ty)?.as_str() {
+            "Json" => generic_arg(ty, "Json").and_then(dsl_type),
+            "Result" => generic_arg(ty, "Result").and_then(json_response),
+            _ => None,
+        },
+    }
+}
+
+// `E` of `Result<T, E>`
+fn result_error(ty: &Type) -> Option<String> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let seg = p.path.segments.last()?;
+    if seg.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(t) => Some(t),
+            _ => None,
+        })
+        .nth(1)
+        .and_then(last_ident)
+}
+
+// Typed headers whose name can't be derived from the type name
+const WELL_KNOWN_HEADERS: &[(&str, &str)] = &[
+    ("ETag", "ETag"),
+    ("WwwAuthenticate", "WWW-Authenticate"),
+    ("Dnt", "DNT"),
+    ("Te", "TE"),
+];
+
+// `UserAgent` -> `User-Agent`, `ContentMD5` -> `Content-MD5` (acronym runs stay together)
+fn header_name(ident: &str) -> String {
+    if let Some((_, name)) = WELL_KNOWN_HEADERS.iter().find(|(ty, _)| *ty == ident) {
+        return name.to_string();
+    }
+    let chars: Vec<char> = ident.chars().collect();
+    let mut name = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if !prev.is_uppercase() || next_lower {
+                name.push('-');
+            }
+        }
+        name.push(c);
+    }
+    name
+}
+
+fn last_ident(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(r) => last_ident(&r.elem),
+        _ => None,
+    }
+}
+
+// First type argument of `ty` if its last segment is `wrapper`.
+fn generic_arg<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let seg = p.path.segments.last()?;
+    if seg.ident != wrapper {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|a| match a {
+        syn::GenericArgument::Type(t) => Some(t),
+        _ => None,
+    })
+}
+
+// Type text as understood by the route DSL: custom generics get the `$` prefix
+// so they are monomorphized (`$Page<User>`).
+fn dsl_type(ty: &Type) -> Option<String> {
+    const STD: &[&str] = &[
+        "Option", "Vec", "Box", "Arc", "Rc", "Cow", "HashMap", "BTreeMap", "HashSet",
+    ];
+    let rendered = type_to_string(ty)?;
+    let is_custom_generic = rendered.contains('<')
+        && last_ident(ty).is_some_and(|ident| !STD.contains(&ident.as_str()));
+    if is_custom_generic {
+        Some(format!("${}", rendered))
+    } else {
+        Some(rendered)
+    }
+}
+
+fn has_param_line(doc_lines: &[String], directive: &str, name: &str) -> bool {
+    doc_lines.iter().any(|l| {
+        l.trim()
+            .strip_prefix(directive)
+            .and_then(|rest| rest.trim().split(':').next())
+            .is_some_and(|n| n.trim() == name)
+    })
+}
+
+// Type of the handler argument bound to `name`, rendered for the route DSL.
+fn arg_type(sig: &Signature, name: &str) -> Option<String> {
+    sig.inputs.iter().find_map(|input| {
+        let FnArg::Typed(arg) = input else {
+            return None;
+        };
+        match &*arg.pat {
+            Pat::Ident(p) if p.ident == name => type_to_string(&arg.ty),
+            _ => None,
+        }
+    })
+}
+
+/// Renders a type as compact source text (`Option<u32>`), dropping references
+/// and module paths (schemas are referenced by their bare name).
+pub fn type_to_string(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Reference(r) => type_to_string(&r.elem),
+        Type::Paren(p) => type_to_string(&p.elem),
+        Type::Group(g) => type_to_string(&g.elem),
+        Type::Path(p) => {
+            let seg = p.path.segments.last()?;
+            let mut s = seg.ident.to_string();
+            if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
+                let inner: Vec<String> = args
+                    .args
+                    .iter()
+                    .filter_map(|a| match a {
+                        syn::GenericArgument::Type(t) => type_to_string(t),
+                        _ => None,
+                    })
+                    .collect();
+                if !inner.is_empty() {
+                    s.push_str(&format!("<{}>", inner.join(",")));
+                }
+            }
+            Some(s)
+        }
+        Type::Tuple(t) => {
+            let inner: Option<Vec<String>> = t.elems.iter().map(type_to_string).collect();
+            Some(format!("({})", inner?.join(",")))
+        }
+        Type::Slice(s) => type_to_string(&s.elem).map(|t| format!("[{}]", t)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_path_syntax() {
+        assert_eq!(convert_path_syntax("/users/<id>"), "/users/{id}");
+        assert_eq!(convert_path_syntax("/files/<path..>"), "/files/{path}");
+        assert_eq!(convert_path_syntax(r"/users/{id:\d+}"), "/users/{id}");
+    }
+
+    #[test]
+    fn test_header_name() {
+        assert_eq!(header_name("UserAgent"), "User-Agent");
+        assert_eq!(header_name("ETag"), "ETag");
+        assert_eq!(header_name("IfMatch"), "If-Match");
+        assert_eq!(header_name("WWWAuthenticate"), "WWW-Authenticate");
+        assert_eq!(header_name("WwwAuthenticate"), "WWW-Authenticate");
+        assert_eq!(header_name("ContentMD5"), "Content-MD5");
+        assert_eq!(header_name("XRequestID"), "X-Request-ID");
+    }
+
+    #[test]
+    fn test_type_to_string() {
+        let ty: Type = syn::parse_str("Option<&'a str>").unwrap();
+        assert_eq!(type_to_string(&ty).unwrap(), "Option<str>");
+        let ty: Type = syn::parse_str("std::path::PathBuf").unwrap();
+        assert_eq!(type_to_string(&ty).unwrap(), "PathBuf");
+        let ty: Type = syn::parse_str("(uuid::Uuid, u32)").unwrap();
+        assert_eq!(type_to_string(&ty).unwrap(), "(Uuid,u32)");
+    }
+}
@@ -26,6 +26,8 @@ pub struct Registry {
     pub schemas: HashMap<String, String>,
     /// Concrete schemas generated from generics (e.g. Page_User)
     pub concrete_schemas: HashMap<String, String>,
+    /// Error enums: JSON map `status -> response` (see `@errors`)
+    pub error_responses: HashMap<String, String>,
 }
 
 impl Registry {
@@ -56,4 +58,8 @@ impl Registry {
     pub fn insert_schema(&mut self, name: String, content: String) {
         self.schemas.insert(name, content);
     }
+
+    pub fn insert_error_responses(&mut self, name: String, content: String) {
+        self.error_responses.insert(name, content);
+    }
 }
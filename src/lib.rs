@@ -1,13 +1,18 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::collapsible_if)]
 pub mod config;
+pub mod constants;
+pub mod deprecation;
 pub mod doc_parser;
 pub mod dsl;
 pub mod error;
+pub mod examples;
 pub mod generics;
+pub mod handlers;
 pub mod index;
 pub mod merger;
 pub mod preprocessor;
+pub mod router;
 pub mod scanner;
 pub mod type_mapper;
 pub mod visitor;
@@ -26,6 +31,11 @@ pub struct Generator {
     schema_outputs: Vec<PathBuf>,
     path_outputs: Vec<PathBuf>,
     fragment_outputs: Vec<PathBuf>,
+    example_outputs: Vec<PathBuf>,
+    synthesize_examples: bool,
+    deprecation_report: bool,
+    fail_on_sunset: bool,
+    scan_options: scanner::ScanOptions,
 }
 
 impl Generator {
@@ -54,6 +64,36 @@ impl Generator {
         if let Some(output_fragments) = config.output_fragments {
             self.fragment_outputs.extend(output_fragments);
         }
+        if let Some(output_examples) = config.output_examples {
+            self.example_outputs.extend(output_examples);
+        }
+        if let Some(synthesize_examples) = config.synthesize_examples {
+            self.synthesize_examples = synthesize_examples;
+        }
+        if let Some(infer_routes) = config.infer_routes {
+            self.scan_options.infer_routes = infer_routes;
+        }
+        if let Some(route_defaults) = config.route_defaults {
+            self.scan_options.route_defaults.extend(route_defaults);
+        }
+        if let Some(deprecation_report) = config.deprecation_report {
+            self.deprecation_report = deprecation_report;
+        }
+        if let Some(fail_on_sunset) = config.fail_on_sunset {
+            self.fail_on_sunset = fail_on_sunset;
+        }
+        if let Some(response_envelope) = config.response_envelope {
+            self = self.response_envelope(response_envelope);
+        }
+        if let Some(error_envelope) = config.error_envelope {
+            self = self.error_envelope(error_envelope);
+        }
+        if let Some(operation_id) = config.operation_id {
+            match dsl::OperationIdStrategy::parse(&operation_id) {
+                Some(strategy) => self.scan_options.operation_ids = strategy,
+                None => log::warn!("Unknown operationId strategy '{}'", operation_id),
+            }
+        }
         self
     }
 
@@ -93,16 +133,80 @@ impl Generator {
         self
     }
 
+    /// Appends an output file path for synthesized schema examples.
+    pub fn output_examples<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.example_outputs.push(path.into());
+        self
+    }
+
+    /// Generates deterministic examples for schemas that lack one and
+    /// writes them into the `example` keyword.
+    pub fn synthesize_examples(mut self, enabled: bool) -> Self {
+        self.synthesize_examples = enabled;
+        self
+    }
+
+    /// Infers routes for documented handlers from axum `Router` builder chains.
+    pub fn infer_routes(mut self, enabled: bool) -> Self {
+        self.scan_options.infer_routes = enabled;
+        self
+    }
+
+    /// Adds a `@default-*` line applied to every route, e.g.
+    /// `"@default-response 401 $Problem"`. Module-level defaults and the
+    /// route's own directives take precedence.
+    pub fn route_default(mut self, line: impl Into<String>) -> Self {
+        self.scan_options.route_defaults.push(line.into());
+        self
+    }
+
+    /// Logs every deprecated operation with its sunset date and replacement.
+    pub fn deprecation_report(mut self, enabled: bool) -> Self {
+        self.deprecation_report = enabled;
+        self
+    }
+
+    /// Fails generation (after writing outputs) when a deprecated operation is
+    /// past its `sunset=` date. Implies the deprecation report.
+    pub fn fail_on_sunset(mut self, enabled: bool) -> Self {
+        self.fail_on_sunset = enabled;
+        self
+    }
+
+    /// Wraps 2xx JSON responses in the given blueprint (`$ApiResponse<T>`).
+    /// Module docs override it with `@response-envelope`, routes opt out with `@raw`.
+    pub fn response_envelope(mut self, blueprint: impl Into<String>) -> Self {
+        self.scan_options
+            .route_defaults
+            .push(format!("@response-envelope {}", blueprint.into()));
+        self
+    }
+
+    /// Uses the given schema as body of non-2xx responses that declare none.
+    pub fn error_envelope(mut self, schema: impl Into<String>) -> Self {
+        self.scan_options
+            .route_defaults
+            .push(format!("@error-envelope {}", schema.into()));
+        self
+    }
+
+    /// Sets how operationIds are derived for routes without `@operation-id`.
+    pub fn operation_ids(mut self, strategy: dsl::OperationIdStrategy) -> Self {
+        self.scan_options.operation_ids = strategy;
+        self
+    }
+
     /// Executes the generation process.
     pub fn generate(self) -> Result<()> {
         if self.outputs.is_empty()
             && self.schema_outputs.is_empty()
             && self.path_outputs.is_empty()
             && self.fragment_outputs.is_empty()
+            && self.example_outputs.is_empty()
         {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                "At least one output path (output, output_schemas, output_paths, or output_fragments) is required",
+                "At least one output path (output, output_schemas, output_paths, output_fragments, or output_examples) is required",
             )
             .into());
         }
@@ -113,7 +217,8 @@ impl Generator {
             self.inputs,
             self.includes
         );
-        let (snippets, registry) = scanner::scan_directories(&self.inputs, &self.includes)?;
+        let (snippets, registry) =
+            scanner::scan_directories_with(&self.inputs, &self.includes, &self.scan_options)?;
 
         // 2. Merge (Relaxed - may return empty map if no root)
         log::info!("Merging {} snippets", snippets.len());
@@ -138,6 +243,51 @@ impl Generator {
             }
         }
 
+        // Dangling links: fatal for the full spec, warnings for partial outputs
+        let dangling: Vec<String> = merger::dangling_links(&merged_value)
+            .into_iter()
+            .map(|(link, operation_id)| format!("'{}' -> {}", link, operation_id))
+            .collect();
+        if !dangling.is_empty() {
+            if !self.outputs.is_empty() {
+                return Err(error::Error::DanglingLink { links: dangling });
+            }
+            for link in &dangling {
+                log::warn!("Link {} references an unknown operationId", link);
+            }
+        }
+
+        // Deprecation report (opt-in)
+        let mut passed_sunsets = Vec::new();
+        if self.deprecation_report || self.fail_on_sunset {
+            let today = deprecation::today();
+            let deprecated = deprecation::deprecated_operations(&merged_value);
+            log::info!("{} deprecated operations", deprecated.len());
+            for operation in deprecated {
+                if operation.sunset_passed(&today) {
+                    log::error!("Sunset passed: {}", operation);
+                    passed_sunsets.push(operation.to_string());
+                } else {
+                    log::warn!("Deprecated: {}", operation);
+                }
+            }
+        }
+
+        // Example synthesis (opt-in): runs on the merged, monomorphized spec
+        if self.synthesize_examples || !self.example_outputs.is_empty() {
+            let examples = examples::synthesize_examples(&merged_value);
+            log::info!("Synthesized {} schema examples", examples.len());
+
+            for output in &self.example_outputs {
+                self.write_file(output, &examples)?;
+                log::info!("Written examples to {:?}", output);
+            }
+
+            if self.synthesize_examples {
+                examples::apply_examples(&mut merged_value, &examples);
+            }
+        }
+
         // Strategy 1: Full Spec (Strict Validation)
         if !self.outputs.is_empty() {
             if let serde_yaml_ng::Value::Mapping(map) = &merged_value {
@@ -250,6 +400,12 @@ impl Generator {
             }
         }
 
+        if self.fail_on_sunset && !passed_sunsets.is_empty() {
+            return Err(error::Error::SunsetPassed {
+                operations: passed_sunsets,
+            });
+        }
+
         Ok(())
     }
 
@@ -1,11 +1,15 @@
 use crate::error::{Error, Result};
 use crate::scanner::Snippet;
 use serde_yaml_ng::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Merges multiple OpenAPI YAML/JSON fragments into a single Value.
 pub fn merge_openapi(snippets: Vec<Snippet>) -> Result<Value> {
     let mut root: Option<Value> = None;
     let mut others: Vec<Value> = Vec::new();
+    // operationId -> (operation location, source file, line)
+    let mut operation_ids: HashMap<String, (String, PathBuf, usize)> = HashMap::new();
 
     for snippet in snippets {
         let value: Value = match serde_yaml_ng::from_str(&snippet.content) {
@@ -30,6 +34,28 @@ pub fn merge_openapi(snippets: Vec<Snippet>) -> Result<Value> {
             }
         };
 
+        for (operation_id, location) in operation_ids_of(&value) {
+            match operation_ids.get(&operation_id) {
+                // Fragments extending the same operation share its id
+                Some((seen, _, _)) if *seen == location => {}
+                Some((_, file, line)) => {
+                    return Err(Error::DuplicateOperationId {
+                        operation_id,
+                        first_file: file.clone(),
+                        first_line: *line,
+                        second_file: snippet.file_path.clone(),
+                        second_line: snippet.line_number,
+                    });
+                }
+                None => {
+                    operation_ids.insert(
+                        operation_id,
+                        (location, snippet.file_path.clone(), snippet.line_number),
+                    );
+                }
+            }
+        }
+
         if is_root(&value) {
             if root.is_some() {
                 return Err(Error::MultipleRootsFound);
@@ -47,9 +73,170 @@ pub fn merge_openapi(snippets: Vec<Snippet>) -> Result<Value> {
         deep_merge(&mut root, other);
     }
 
+    resolve_inheritance(&mut root);
+
+    // OpenAPI 3.2 has a native `itemSchema` for streamed media types
+    if root
+        .get("openapi")
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.starts_with("3.2"))
+    {
+        rename_key(&mut root, crate::dsl::ITEM_SCHEMA_KEY, "itemSchema");
+    }
+
     Ok(root)
 }
 
+/// `(operationId, "path method")` of every operation in `paths` and `webhooks`.
+fn operation_ids_of(value: &Value) -> Vec<(String, String)> {
+    let mut ids = Vec::new();
+    for section in ["paths", "webhooks"] {
+        let Some(items) = value.get(section).and_then(|p| p.as_mapping()) else {
+            continue;
+        };
+        for (path, item) in items {
+            let Some(item) = item.as_mapping() else {
+                continue;
+            };
+            for (method, operation) in item {
+                if let Some(id) = operation.get("operationId").and_then(|id| id.as_str()) {
+                    let location = format!(
+                        "{} {}",
+                        path.as_str().unwrap_or_default(),
+                        method.as_str().unwrap_or_default()
+                    );
+                    ids.push((id.to_string(), location));
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Response links whose `operationId` matches no operation of the spec,
+/// as `(link name, operationId)` pairs.
+pub fn dangling_links(root: &Value) -> Vec<(String, String)> {
+    let operations = || {
+        root.get("paths")
+            .and_then(|p| p.as_mapping())
+            .into_iter()
+            .flat_map(|paths| paths.values())
+            .filter_map(|item| item.as_mapping())
+            .flat_map(|item| item.values())
+    };
+
+    let known: Vec<&str> = operations()
+        .filter_map(|op| op.get("operationId").and_then(|id| id.as_str()))
+        .collect();
+
+    let mut dangling = Vec::new();
+    for responses in operations().filter_map(|op| op.get("responses")?.as_mapping()) {
+        for response in responses.values() {
+            let Some(links) = response.get("links").and_then(|l| l.as_mapping()) else {
+                continue;
+            };
+            for (name, link) in links {
+                let Some(operation_id) = link.get("operationId").and_then(|id| id.as_str()) else {
+                    continue;
+                };
+                if !known.contains(&operation_id) {
+                    dangling.push((
+                        name.as_str().unwrap_or_default().to_string(),
+                        operation_id.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+    dangling
+}
+
+/// Registers `@openapi extends` subtypes in their base schema.
+/// Subtypes carry an `x-oas-forge-extends` marker; if the base declares a
+/// `discriminator`, the subtype is appended to its `oneOf` and `mapping`.
+/// The marker is always stripped.
+fn resolve_inheritance(root: &mut Value) {
+    let Some(Value::Mapping(schemas)) = root
+        .get_mut("components")
+        .and_then(|c| c.get_mut("schemas"))
+    else {
+        return;
+    };
+
+    let marker_key = Value::String("x-oas-forge-extends".to_string());
+    let mut subtypes = Vec::new();
+    for (name, schema) in schemas.iter_mut() {
+        if let (Value::String(name), Value::Mapping(schema_map)) = (name, schema) {
+            if let Some(marker) = schema_map.remove(&marker_key) {
+                let base = marker.get("base").and_then(|b| b.as_str());
+                let mapping = marker.get("mapping").and_then(|m| m.as_str());
+                if let Some(base) = base {
+                    subtypes.push((
+                        name.clone(),
+                        base.to_string(),
+                        mapping.unwrap_or(name).to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (name, base, mapping) in subtypes {
+        let Some(Value::Mapping(base_map)) = schemas.get_mut(base.as_str()) else {
+            log::warn!("Base schema '{}' of '{}' not found", base, name);
+            continue;
+        };
+        if !base_map.contains_key("discriminator") {
+            continue;
+        }
+
+        let sub_ref = format!("#/components/schemas/{}", name);
+        let mut ref_map = serde_yaml_ng::Mapping::new();
+        ref_map.insert(
+            Value::String("$ref".to_string()),
+            Value::String(sub_ref.clone()),
+        );
+        let ref_value = Value::Mapping(ref_map);
+
+        let one_of = base_map
+            .entry(Value::String("oneOf".to_string()))
+            .or_insert_with(|| Value::Sequence(Vec::new()));
+        if let Value::Sequence(seq) = one_of {
+            if !seq.contains(&ref_value) {
+                seq.push(ref_value);
+            }
+        }
+
+        if let Some(Value::Mapping(disc)) = base_map.get_mut("discriminator") {
+            let mapping_map = disc
+                .entry(Value::String("mapping".to_string()))
+                .or_insert_with(|| Value::Mapping(serde_yaml_ng::Mapping::new()));
+            if let Value::Mapping(m) = mapping_map {
+                m.insert(Value::String(mapping), Value::String(sub_ref));
+            }
+        }
+    }
+}
+
+fn rename_key(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::Mapping(map) => {
+            if let Some(inner) = map.remove(from) {
+                map.insert(Value::String(to.to_string()), inner);
+            }
+            for inner in map.values_mut() {
+                rename_key(inner, from, to);
+            }
+        }
+        Value::Sequence(seq) => {
+            for inner in seq {
+                rename_key(inner, from, to);
+            }
+        }
+        _ => {}
+    }
+}
+
 fn is_root(value: &Value) -> bool {
     if let Value::Mapping(map) = value {
         map.contains_key("openapi") && map.contains_key("info")
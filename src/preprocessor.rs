@@ -108,12 +108,14 @@ pub fn preprocess(content: &str, registry: &Registry) -> String {
 
     // Phase B: Structural Merge
     // Try to parse as YAML Value. If fails, return textual output (fallback).
+    // Plain text (e.g. a route DSL block starting with a summary line) may parse as a
+    // YAML scalar; only mappings and sequences are structural.
     match serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&phase_a_output) {
-        Ok(mut root) => {
+        Ok(mut root) if root.is_mapping() || root.is_sequence() => {
             process_value(&mut root, registry);
             serde_yaml_ng::to_string(&root).unwrap_or(phase_a_output)
         }
-        Err(_) => {
+        _ => {
             // Likely a partial snippet (list item or partial object).
             // Return text, but @extend markers are present.
             // If it's a snippet, @extend might not work fully structurally.
@@ -0,0 +1,649 @@
+use crate::scanner::{ParsedFile, Snippet};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Block, Expr, ItemFn, Pat, Stmt};
+
+const METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace",
+];
+
+/// A route discovered in an axum `Router` builder chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct InferredRoute {
+    method: String,
+    path: String,
+    /// Handler path as written (`list`, `users::list`)
+    handler: String,
+    /// Module of the router that references the handler
+    module: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum RouterNode {
+    Route {
+        path: String,
+        handlers: Vec<(String, String)>,
+    },
+    Nest {
+        prefix: String,
+        target: RouterRef,
+    },
+    Merge(RouterRef),
+}
+
+#[derive(Debug, Clone)]
+enum RouterRef {
+    Inline(Vec<RouterNode>),
+    Call(String),
+}
+
+/// A function building a `Router`, with the module it is declared in.
+#[derive(Debug, Clone)]
+struct RouterDef {
+    module: Vec<String>,
+    nodes: Vec<RouterNode>,
+}
+
+/// Collects router definitions (per function) and documented handlers without `@route`,
+/// keyed by their module path (`users::list`).
+#[derive(Default)]
+struct RouterCollector {
+    routers: HashMap<String, RouterDef>,
+    handlers: HashMap<String, HandlerDoc>,
+    /// Module path of the file followed by the inline modules being visited
+    module: Vec<String>,
+    /// Inline modules being visited (below the file)
+    inline_module: Vec<String>,
+    /// Scoped directives (`@route-prefix`, `@default-*`) of the module being visited
+    scoped_directives: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct HandlerDoc {
+    doc_lines: Vec<String>,
+    line: usize,
+    sig: syn::Signature,
+    scoped_directives: Vec<String>,
+    inline_module: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for RouterCollector {
+    fn visit_item_fn(&mut self, i: &'ast ItemFn) {
+        let name = qualify(&self.module, &i.sig.ident.to_string());
+        if let Some(nodes) = router_of_block(&i.block) {
+            let module = self.module.clone();
+            self.routers
+                .insert(name.clone(), RouterDef { module, nodes });
+        }
+
+        let doc_lines = crate::doc_parser::extract_route_doc_comments(&i.attrs);
+        let has_docs = doc_lines.iter().any(|l| !l.trim().is_empty());
+        let is_explicit = doc_lines
+            .iter()
+            .any(|l| crate::dsl::is_operation_line(l) || l.contains("@openapi"));
+        if has_docs && !is_explicit {
+            self.handlers.insert(
+                name,
+                HandlerDoc {
+                    doc_lines,
+                    line: i.span().start().line,
+                    sig: i.sig.clone(),
+                    scoped_directives: self.scoped_directives.clone(),
+                    inline_module: self.inline_module.clone(),
+                },
+            );
+        }
+
+        visit::visit_item_fn(self, i);
+    }
+
+    fn visit_item_mod(&mut self, i: &'ast syn::ItemMod) {
+        let old_len = self.scoped_directives.len();
+        self.scoped_directives
+            .extend(crate::visitor::scoped_directives(&i.attrs));
+        self.module.push(i.ident.to_string());
+        self.inline_module.push(i.ident.to_string());
+        visit::visit_item_mod(self, i);
+        self.module.pop();
+        self.inline_module.pop();
+        self.scoped_directives.truncate(old_len);
+    }
+}
+
+// `users` + `list` -> `users::list`
+fn qualify(module: &[String], name: &str) -> String {
+    if module.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", module.join("::"), name)
+    }
+}
+
+/// Resolves a path as written in a router (`list`, `users::list`, `crate::users::list`,
+/// `super::list`) from `module` to a key of `items`. Bare and relative paths prefer
+/// the same module, then any module ending with the path (`use` imports).
+/// Ambiguous references are reported and skipped.
+fn resolve<'a, T>(
+    reference: &str,
+    module: &[String],
+    items: &'a HashMap<String, T>,
+) -> Option<&'a str> {
+    let mut segments: Vec<&str> = reference.split("::").collect();
+    let mut base = None;
+    while let Some(&first) = segments.first() {
+        match first {
+            "crate" => base = Some(Vec::new()),
+            "self" => base = Some(base.unwrap_or_else(|| module.to_vec())),
+            "super" => {
+                let mut parent = base.unwrap_or_else(|| module.to_vec());
+                parent.pop();
+                base = Some(parent);
+            }
+            _ => break,
+        }
+        segments.remove(0);
+    }
+    let reference = segments.join("::");
+    if let Some(base) = base {
+        let key = qualify(&base, &reference);
+        return items.get_key_value(&key).map(|(k, _)| k.as_str());
+    }
+
+    if let Some((key, _)) = items.get_key_value(&qualify(module, &reference)) {
+        return Some(key);
+    }
+    let suffix = format!("::{}", reference);
+    let mut candidates: Vec<&String> = items
+        .keys()
+        .filter(|key| **key == reference || key.ends_with(&suffix))
+        .collect();
+    candidates.sort();
+    match candidates.as_slice() {
+        [] => None,
+        [only] => Some(only.as_str()),
+        _ => {
+            log::warn!(
+                "Ambiguous reference '{}' in module '{}' matches {:?}; qualify it with its module path",
+                reference,
+                module.join("::"),
+                candidates
+            );
+            None
+        }
+    }
+}
+
+/// Scans parsed files for axum `Router` chains and emits route DSL snippets for
+/// documented handlers that have no explicit `@route`.
+/// `scoped_directives` holds the `@route-prefix` / `@default-*` lines each file
+/// inherits from its parent modules.
+pub fn infer_route_snippets(
+    files: &[ParsedFile],
+    scoped_directives: &HashMap<PathBuf, Vec<String>>,
+) -> Vec<Snippet> {
+    let mut routers = HashMap::new();
+    let mut handlers: HashMap<String, (HandlerDoc, PathBuf)> = HashMap::new();
+
+    for file in files {
+        let mut collector = RouterCollector {
+            module: file
+                .module
+                .split("::")
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+            scoped_directives: scoped_directives
+                .get(&file.path)
+                .cloned()
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        collector
+            .scoped_directives
+            .extend(crate::visitor::scoped_directives(&file.syntax.attrs));
+        collector.visit_file(&file.syntax);
+        routers.extend(collector.routers);
+        for (name, doc) in collector.handlers {
+            handlers.insert(name, (doc, file.path.clone()));
+        }
+    }
+
+    let routes = expand_routers(&routers);
+    let mut snippets = Vec::new();
+    let mut seen_handlers: HashMap<String, usize> = HashMap::new();
+
+    for route in &routes {
+        let Some(key) = resolve(&route.handler, &route.module, &handlers) else {
+            continue;
+        };
+        let (doc, file) = &handlers[key];
+        let name = doc.sig.ident.to_string();
+
+        // Same-named handlers in different modules get distinct operationIds too
+        let count = seen_handlers.entry(name.clone()).or_insert(0);
+        *count += 1;
+        let operation_id = if *count == 1 {
+            name.clone()
+        } else {
+            format!("{}_{}", name, count)
+        };
+
+        log::info!(
+            "Inferred route {} {} for handler '{}'",
+            route.method.to_uppercase(),
+            route.path,
+            key
+        );
+
+        let mut lines = doc.scoped_directives.clone();
+        if !doc.inline_module.is_empty() {
+            lines.push(format!("@infer module {}", doc.inline_module.join("::")));
+        }
+        lines.push(format!(
+            "@route {} {}",
+            route.method.to_uppercase(),
+            route.path
+        ));
+        lines.extend(doc.doc_lines.iter().cloned());
+        lines.extend(crate::handlers::signature_lines(&doc.sig, true));
+
+        snippets.push(Snippet {
+            content: lines.join("\n"),
+            file_path: file.clone(),
+            line_number: doc.line,
+            operation_id: Some(operation_id),
+        });
+    }
+
+    snippets
+}
+
+/// Resolves nests and merges starting from the routers no other router refers to.
+fn expand_routers(routers: &HashMap<String, RouterDef>) -> Vec<InferredRoute> {
+    let mut referenced = HashSet::new();
+    for def in routers.values() {
+        collect_calls(&def.nodes, &def.module, routers, &mut referenced);
+    }
+
+    let mut roots: Vec<&String> = routers
+        .keys()
+        .filter(|name| !referenced.contains(name.as_str()))
+        .collect();
+    roots.sort();
+
+    let mut routes = Vec::new();
+    for root in roots {
+        let mut stack = vec![root.clone()];
+        expand(&routers[root], "", routers, &mut stack, &mut routes);
+    }
+
+    let mut seen = HashSet::new();
+    routes.retain(|r| seen.insert(r.clone()));
+    routes
+}
+
+fn collect_calls<'a>(
+    nodes: &[RouterNode],
+    module: &[String],
+    routers: &'a HashMap<String, RouterDef>,
+    out: &mut HashSet<&'a str>,
+) {
+    for node in nodes {
+        match node {
+            RouterNode::Nest { target, .. } | RouterNode::Merge(target) => match target {
+                RouterRef::Call(name) => {
+                    if let Some(key) = resolve(name, module, routers) {
+                        out.insert(key);
+                    }
+                }
+                RouterRef::Inline(inner) => collect_calls(inner, module, routers, out),
+            },
+            RouterNode::Route { .. } => {}
+        }
+    }
+}
+
+fn expand(
+    def: &RouterDef,
+    prefix: &str,
+    routers: &HashMap<String, RouterDef>,
+    stack: &mut Vec<String>,
+    out: &mut Vec<InferredRoute>,
+) {
+    expand_nodes(&def.nodes, &def.module, prefix, routers, stack, out);
+}
+
+fn expand_nodes(
+    nodes: &[RouterNode],
+    module: &[String],
+    prefix: &str,
+    routers: &HashMap<String, RouterDef>,
+    stack: &mut Vec<String>,
+    out: &mut Vec<InferredRoute>,
+) {
+    for node in nodes {
+        match node {
+            RouterNode::Route { path, handlers } => {
+                let full = normalize_path(&join_paths(prefix, path));
+                for (method, handler) in handlers {
+                    out.push(InferredRoute {
+                        method: method.clone(),
+                        path: full.clone(),
+                        handler: handler.clone(),
+                        module: module.to_vec(),
+                    });
+                }
+            }
+            RouterNode::Nest {
+                prefix: nested,
+                target,
+            } => {
+                let joined = join_paths(prefix, nested);
+                expand_ref(target, module, &joined, routers, stack, out);
+            }
+            RouterNode::Merge(target) => expand_ref(target, module, prefix, routers, stack, out),
+        }
+    }
+}
+
+fn expand_ref(
+    target: &RouterRef,
+    module: &[String],
+    prefix: &str,
+    routers: &HashMap<String, RouterDef>,
+    stack: &mut Vec<String>,
+    out: &mut Vec<InferredRoute>,
+) {
+    match target {
+        RouterRef::Inline(nodes) => expand_nodes(nodes, module, prefix, routers, stack, out),
+        RouterRef::Call(name) => {
+            let Some(key) = resolve(name, module, routers) else {
+                return;
+            };
+            if stack.iter().any(|s| s == key) {
+                log::warn!("Recursive router reference '{}' ignored", name);
+                return;
+            }
+            stack.push(key.to_string());
+            expand(&routers[key], prefix, routers, stack, out);
+            stack.pop();
+        }
+    }
+}
+
+// The router a function builds: its tail expression, or the last router bound with `let`.
+fn router_of_block(block: &Block) -> Option<Vec<RouterNode>> {
+    let mut vars: HashMap<String, Vec<RouterNode>> = HashMap::new();
+    let mut last = None;
+
+    for stmt in &block.stmts {
+        match stmt {
+            Stmt::Local(local) => {
+                let Some(name) = pat_ident(&local.pat) else {
+                    continue;
+                };
+                if let Some(init) = &local.init {
+                    if let Some(nodes) = parse_router(&init.expr, &vars) {
+                        vars.insert(name, nodes.clone());
+                        last = Some(nodes);
+                    }
+                }
+            }
+            Stmt::Expr(Expr::Assign(assign), _) => {
+                if let Expr::Path(p) = &*assign.left {
+                    if let Some(ident) = p.path.get_ident() {
+                        if let Some(nodes) = parse_router(&assign.right, &vars) {
+                            vars.insert(ident.to_string(), nodes.clone());
+                            last = Some(nodes);
+                        }
+                    }
+                }
+            }
+            Stmt::Expr(expr, semi) => {
+                let value = match expr {
+                    Expr::Return(ret) => ret.expr.as_deref(),
+                    e if semi.is_none() => Some(e),
+                    _ => None,
+                };
+                if let Some(nodes) = value.and_then(|e| parse_router(e, &vars)) {
+                    last = Some(nodes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    last
+}
+
+fn pat_ident(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(p) => Some(p.ident.to_string()),
+        Pat::Type(t) => pat_ident(&t.pat),
+        _ => None,
+    }
+}
+
+fn parse_router(expr: &Expr, vars: &HashMap<String, Vec<RouterNode>>) -> Option<Vec<RouterNode>> {
+    match expr {
+        Expr::MethodCall(mc) => {
+            let method = mc.method.to_string();
+            let args: Vec<&Expr> = mc.args.iter().collect();
+            let is_builder = matches!(
+                method.as_str(),
+                "route" | "route_service" | "nest" | "merge"
+            );
+
+            let base = parse_router(&mc.receiver, vars);
+            if !is_builder {
+                // layer, with_state, fallback, ... keep the routes of the receiver
+                return base;
+            }
+
+            let mut nodes = base
+                .or_else(|| {
+                    call_name(&mc.receiver)
+                        .map(|name| vec![RouterNode::Merge(RouterRef::Call(name))])
+                })
+                .unwrap_or_default();
+
+            match (method.as_str(), args.as_slice()) {
+                ("route" | "route_service", [path, method_router, ..]) => {
+                    if let Some(path) = lit_str(path) {
+                        nodes.push(RouterNode::Route {
+                            path,
+                            handlers: parse_method_router(method_router),
+                        });
+                    }
+                }
+                ("nest", [prefix, target, ..]) => {
+                    if let (Some(prefix), Some(target)) = (lit_str(prefix), parse_ref(target, vars))
+                    {
+                        nodes.push(RouterNode::Nest { prefix, target });
+                    }
+                }
+                ("merge", [target, ..]) => {
+                    if let Some(target) = parse_ref(target, vars) {
+                        nodes.push(RouterNode::Merge(target));
+                    }
+                }
+                _ => {}
+            }
+            Some(nodes)
+        }
+        Expr::Call(call) => {
+            let Expr::Path(func) = &*call.func else {
+                return None;
+            };
+            let segments: Vec<String> = func
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let is_router_new = segments.len() >= 2
+                && segments[segments.len() - 2] == "Router"
+                && segments[segments.len() - 1] == "new";
+            is_router_new.then(Vec::new)
+        }
+        Expr::Path(p) => p
+            .path
+            .get_ident()
+            .and_then(|ident| vars.get(&ident.to_string()).cloned()),
+        Expr::Paren(p) => parse_router(&p.expr, vars),
+        Expr::Group(g) => parse_router(&g.expr, vars),
+        _ => None,
+    }
+}
+
+fn parse_ref(expr: &Expr, vars: &HashMap<String, Vec<RouterNode>>) -> Option<RouterRef> {
+    if let Some(nodes) = parse_router(expr, vars) {
+        return Some(RouterRef::Inline(nodes));
+    }
+    match expr {
+        Expr::MethodCall(mc) => parse_ref(&mc.receiver, vars),
+        _ => call_name(expr).map(RouterRef::Call),
+    }
+}
+
+// Path of the function called by `routes()` / `api::routes()`.
+fn call_name(expr: &Expr) -> Option<String> {
+    if let Expr::Call(call) = expr {
+        if let Expr::Path(func) = &*call.func {
+            return Some(path_string(&func.path));
+        }
+    }
+    None
+}
+
+// `users::list`
+fn path_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+// `get(a).post(b)`, `axum::routing::get(a)`, `on(MethodFilter::GET, a)`, `get_service(a)`
+fn parse_method_router(expr: &Expr) -> Vec<(String, String)> {
+    match expr {
+        Expr::Call(call) => {
+            let Expr::Path(func) = &*call.func else {
+                return Vec::new();
+            };
+            let Some(name) = func.path.segments.last().map(|s| s.ident.to_string()) else {
+                return Vec::new();
+            };
+            let args: Vec<&Expr> = call.args.iter().collect();
+            method_handler(&name, &args).into_iter().collect()
+        }
+        Expr::MethodCall(mc) => {
+            let mut handlers = parse_method_router(&mc.receiver);
+            let args: Vec<&Expr> = mc.args.iter().collect();
+            handlers.extend(method_handler(&mc.method.to_string(), &args));
+            handlers
+        }
+        Expr::Paren(p) => parse_method_router(&p.expr),
+        Expr::Group(g) => parse_method_router(&g.expr),
+        _ => Vec::new(),
+    }
+}
+
+fn method_handler(name: &str, args: &[&Expr]) -> Option<(String, String)> {
+    if name == "on" {
+        let [filter, handler, ..] = args else {
+            return None;
+        };
+        let Expr::Path(filter) = filter else {
+            return None;
+        };
+        let method = filter
+            .path
+            .segments
+            .last()?
+            .ident
+            .to_string()
+            .to_lowercase();
+        return METHODS
+            .contains(&method.as_str())
+            .then_some((method, handler_name(handler)?));
+    }
+
+    let method = name.strip_suffix("_service").unwrap_or(name);
+    if !METHODS.contains(&method) {
+        return None;
+    }
+    Some((method.to_string(), handler_name(args.first()?)?))
+}
+
+fn handler_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(p) => Some(path_string(&p.path)),
+        _ => None,
+    }
+}
+
+fn lit_str(expr: &Expr) -> Option<String> {
+    if let Expr::Lit(lit) = expr {
+        if let syn::Lit::Str(s) = &lit.lit {
+            return Some(s.value());
+        }
+    }
+    None
+}
+
+pub(crate) fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if path.is_empty() || path == "/" {
+        if prefix.is_empty() {
+            "/".to_string()
+        } else {
+            prefix.to_string()
+        }
+    } else if path.starts_with('/') {
+        format!("{}{}", prefix, path)
+    } else {
+        format!("{}/{}", prefix, path)
+    }
+}
+
+/// Converts axum path syntax (`:id`, `*rest`, `{*rest}`) into OpenAPI templates.
+pub fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if let Some(name) = segment.strip_prefix(':') {
+                format!("{{{}}}", name)
+            } else if let Some(name) = segment.strip_prefix('*') {
+                format!("{{{}}}", name)
+            } else if let Some(name) = segment.strip_prefix("{*") {
+                format!("{{{}", name)
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("/users/:id"), "/users/{id}");
+        assert_eq!(normalize_path("/files/*rest"), "/files/{rest}");
+        assert_eq!(normalize_path("/files/{*rest}"), "/files/{rest}");
+        assert_eq!(normalize_path("/users/{id}"), "/users/{id}");
+    }
+
+    #[test]
+    fn test_join_paths() {
+        assert_eq!(join_paths("", "/"), "/");
+        assert_eq!(join_paths("/api", "/"), "/api");
+        assert_eq!(join_paths("/api/", "/users"), "/api/users");
+    }
+}
@@ -1,12 +1,14 @@
+use crate::constants;
 use crate::dsl;
 use crate::error::{Error, Result};
 use crate::generics::Monomorphizer;
 use crate::index::Registry;
 use crate::preprocessor;
+use crate::router;
 use crate::visitor::{self, ExtractedItem};
 use regex::Regex;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use walkdir::WalkDir;
 
@@ -19,6 +21,27 @@ pub struct Snippet {
     pub operation_id: Option<String>,
 }
 
+/// A Rust source file, read and parsed once and shared by all scanner passes.
+#[derive(Debug, Clone)]
+pub struct ParsedFile {
+    pub path: PathBuf,
+    /// Module path derived from the file location (`users::admin`)
+    pub module: String,
+    pub syntax: syn::File,
+}
+
+/// Optional scanner passes. Build it with `..Default::default()`, new options
+/// are added over time.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Infer `@route` lines for documented handlers from axum `Router` chains.
+    pub infer_routes: bool,
+    /// `@default-*` lines applied to every route (outermost scope).
+    pub route_defaults: Vec<String>,
+    /// Derivation of operationIds for routes without `@operation-id`.
+    pub operation_ids: dsl::OperationIdStrategy,
+}
+
 // DX Macros Preprocessor
 // Implementation of auto-quoting and short-hands.
 pub fn preprocess_macros(snippet: &Snippet, registry: &mut Registry) -> Snippet {
@@ -173,9 +196,99 @@ fn finalize_substitution(content: &str) -> String {
     step1.replace("{{CARGO_PKG_VERSION}}", &version)
 }
 
+/// Module path of a source file relative to its scan root
+/// (`src/users/admin.rs` -> `users::admin`, `main.rs` / `lib.rs` -> ``).
+fn file_module_path(path: &Path, roots: &[PathBuf]) -> String {
+    let Some(relative) = roots.iter().find_map(|root| path.strip_prefix(root).ok()) else {
+        return String::new();
+    };
+    let mut segments: Vec<String> = relative
+        .parent()
+        .into_iter()
+        .flat_map(|dir| dir.components())
+        .filter_map(|c| c.as_os_str().to_str().map(str::to_string))
+        .collect();
+    if segments.first().is_some_and(|s| s == "src") {
+        segments.remove(0);
+    }
+    if let Some(stem) = relative.file_stem().and_then(|s| s.to_str()) {
+        if !matches!(stem, "main" | "lib" | "mod") {
+            segments.push(stem.to_string());
+        }
+    }
+    segments.join("::")
+}
+
+/// Resolves the scoped directives (`@route-prefix`, `@default-*`) every module
+/// file inherits: the parent file's own `//!` lines followed by those on its
+/// `mod name;` declaration.
+fn module_scoped_directives(files: &[ParsedFile]) -> HashMap<PathBuf, Vec<String>> {
+    // child file -> (parent file, directives added by the parent)
+    let mut parents: HashMap<PathBuf, (PathBuf, Vec<String>)> = HashMap::new();
+    for ParsedFile {
+        path, syntax: file, ..
+    } in files
+    {
+        let own = visitor::scoped_directives(&file.attrs);
+        for item in &file.items {
+            let syn::Item::Mod(module) = item else {
+                continue;
+            };
+            if module.content.is_some() {
+                continue;
+            }
+            let mut directives = own.clone();
+            directives.extend(visitor::scoped_directives(&module.attrs));
+
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            let base = if matches!(stem, "main" | "lib" | "mod") {
+                dir
+            } else {
+                dir.join(stem)
+            };
+            let name = module.ident.to_string();
+            for child in [
+                base.join(format!("{}.rs", name)),
+                base.join(&name).join("mod.rs"),
+            ] {
+                parents.insert(child, (path.clone(), directives.clone()));
+            }
+        }
+    }
+
+    let mut resolved = HashMap::new();
+    for child in parents.keys() {
+        let mut chain = Vec::new();
+        let mut current = child;
+        while let Some((parent, directives)) = parents.get(current) {
+            chain.push(directives);
+            current = parent;
+            // Guards against `mod` cycles through `#[path]` tricks
+            if chain.len() > 64 {
+                break;
+            }
+        }
+        let stack: Vec<String> = chain.into_iter().rev().flatten().cloned().collect();
+        if !stack.is_empty() {
+            resolved.insert(child.clone(), stack);
+        }
+    }
+    resolved
+}
+
 pub fn scan_directories(
     roots: &[PathBuf],
     includes: &[PathBuf],
+) -> Result<(Vec<Snippet>, Registry)> {
+    scan_directories_with(roots, includes, &ScanOptions::default())
+}
+
+/// Same as [`scan_directories`], with optional passes enabled via `options`.
+pub fn scan_directories_with(
+    roots: &[PathBuf],
+    includes: &[PathBuf],
+    options: &ScanOptions,
 ) -> Result<(Vec<Snippet>, Registry)> {
     let mut registry = Registry::new();
     let mut operation_snippets: Vec<Snippet> = Vec::new();
@@ -202,12 +315,76 @@ pub fn scan_directories(
         files_found = true;
     }
 
+    // Every Rust file is parsed once and shared by the passes below
+    let mut parsed_files = Vec::new();
+    for path in &all_paths {
+        if path.extension().and_then(|s| s.to_str()) != Some("rs") {
+            continue;
+        }
+        let source = std::fs::read_to_string(path)?;
+        let syntax = syn::parse_file(&source).map_err(|e| Error::Parse {
+            file: path.clone(),
+            source: e,
+        })?;
+        parsed_files.push(ParsedFile {
+            path: path.clone(),
+            module: file_module_path(path, roots),
+            syntax,
+        });
+    }
+
+    // PASS 0: Constant Indexing (const / static items across all files)
+    let mut pending_constants = Vec::new();
+    let mut declared: HashMap<String, Vec<&PathBuf>> = HashMap::new();
+    for file in &parsed_files {
+        for (name, expr) in constants::index_parsed(&file.syntax) {
+            let bare = name.rsplit("::").next().unwrap_or(&name).to_string();
+            declared.entry(bare).or_default().push(&file.path);
+            let qualified = if file.module.is_empty() {
+                name
+            } else {
+                format!("{}::{}", file.module, name)
+            };
+            pending_constants.push((qualified, expr));
+        }
+    }
+    // A bare `{{const NAME}}` refers to the first declaration across the project
+    let mut collisions: Vec<_> = declared
+        .iter()
+        .filter(|(_, files)| files.len() > 1)
+        .collect();
+    collisions.sort();
+    for (name, files) in collisions {
+        log::warn!(
+            "Constant '{}' is declared {} times ({:?}); `{{{{const {}}}}}` uses the first one, \
+             qualify it with its module path to pick another",
+            name,
+            files.len(),
+            files,
+            name
+        );
+    }
+    let constant_values = constants::resolve_constants(pending_constants);
+    for file in &mut parsed_files {
+        constants::substitute_doc_constants(&mut file.syntax, &constant_values);
+    }
+    let parsed_index: HashMap<&PathBuf, &ParsedFile> =
+        parsed_files.iter().map(|f| (&f.path, f)).collect();
+
+    // Scoped directive stacks inherited through `mod name;` declarations
+    let scoped_directives = module_scoped_directives(&parsed_files);
+
     // PASS 1: Indexing
-    for path in all_paths {
+    for path in &all_paths {
         if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
             match ext {
                 "rs" => {
-                    let extracted = visitor::extract_from_file(path.clone())?;
+                    let inherited = scoped_directives.get(path).map_or(&[][..], Vec::as_slice);
+                    let extracted = visitor::extract_from_parsed(
+                        &parsed_index[path].syntax,
+                        &constant_values,
+                        inherited,
+                    );
                     for item in extracted {
                         match item {
                             ExtractedItem::Schema {
@@ -253,11 +430,14 @@ pub fn scan_directories(
                             } => {
                                 registry.insert_blueprint(name, params, content);
                             }
+                            ExtractedItem::ErrorResponses { name, content, .. } => {
+                                registry.insert_error_responses(name, content);
+                            }
                         }
                     }
                 }
                 "json" | "yaml" | "yml" => {
-                    let content = std::fs::read_to_string(&path)?;
+                    let content = std::fs::read_to_string(path)?;
 
                     // Hydrate registry from vendor extensions
                     if let Ok(mut val) = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&content) {
@@ -338,6 +518,14 @@ pub fn scan_directories(
         }
     }
 
+    // PASS 1b: Route Inference (axum Router chains, opt-in)
+    if options.infer_routes {
+        operation_snippets.extend(router::infer_route_snippets(
+            &parsed_files,
+            &scoped_directives,
+        ));
+    }
+
     // PASS 2: Pre-Processing & DSL Compilation
     let mut preprocessed_snippets = Vec::new();
     for snippet in operation_snippets {
@@ -349,19 +537,42 @@ pub fn scan_directories(
 
         // 2c. Compile DSL -> YAML
         let final_content = if let Some(op_id) = &macrod_snippet.operation_id {
-            let lines: Vec<String> = expanded_content.lines().map(|s| s.to_string()).collect();
-            if let Some(yaml) = dsl::parse_route_dsl(&lines, op_id) {
-                yaml
-            } else {
-                // If it was captured as DSL but failed parsing (e.g. no @route?), fallback.
-                // But visitor logic ensures @route exists.
-                // Parsing might fail if parse_route_dsl logic filters it out.
-                expanded_content
+            // Config-level defaults form the outermost scope
+            let mut lines: Vec<String> = options
+                .route_defaults
+                .iter()
+                .map(|line| constants::substitute_constants(line, &constant_values))
+                .collect();
+            let module = file_module_path(&macrod_snippet.file_path, roots);
+            if !module.is_empty() {
+                lines.push(format!("@infer module {}", module));
+            }
+            lines.extend(expanded_content.lines().map(|s| s.to_string()));
+            let ctx = dsl::RouteContext {
+                registry: Some(&registry),
+                operation_ids: Some(&options.operation_ids),
+            };
+            match dsl::parse_route_dsl_with(&lines, op_id, &ctx) {
+                Some(yaml) => yaml,
+                // The DSL parser logs the reason; the raw doc text is not YAML
+                None => {
+                    return Err(Error::InvalidRoute {
+                        file: macrod_snippet.file_path.clone(),
+                        line: macrod_snippet.line_number,
+                    });
+                }
             }
         } else {
             expanded_content
         };
 
+        // 2d. Load External Example Files
+        let final_content = if final_content.contains(visitor::EXAMPLE_FILE_MARKER) {
+            load_example_files(&final_content, &macrod_snippet)?
+        } else {
+            final_content
+        };
+
         preprocessed_snippets.push(Snippet {
             content: final_content,
             file_path: macrod_snippet.file_path,
@@ -424,6 +635,85 @@ pub fn scan_directories(
     Ok((final_snippets, registry))
 }
 
+/// Replaces example file placeholders with the parsed file contents.
+/// Paths are resolved relative to the source file, then to the manifest dir.
+fn load_example_files(content: &str, snippet: &Snippet) -> Result<String> {
+    let mut root: serde_yaml_ng::Value = match serde_yaml_ng::from_str(content) {
+        Ok(v) => v,
+        Err(_) => return Ok(content.to_string()),
+    };
+    replace_example_placeholders(&mut root, snippet)?;
+    let generated = serde_yaml_ng::to_string(&root)?;
+    Ok(generated.trim_start_matches("---\n").to_string())
+}
+
+fn replace_example_placeholders(value: &mut serde_yaml_ng::Value, snippet: &Snippet) -> Result<()> {
+    match value {
+        serde_yaml_ng::Value::Mapping(map) => {
+            if let Some(serde_yaml_ng::Value::String(file)) = map.get(visitor::EXAMPLE_FILE_MARKER)
+            {
+                // The directive line travels with the placeholder; the item line otherwise
+                let line = map
+                    .get(visitor::EXAMPLE_LINE_MARKER)
+                    .and_then(|l| l.as_u64())
+                    .map_or(snippet.line_number, |l| l as usize);
+                *value = read_example_file(file, line, snippet)?;
+                return Ok(());
+            }
+            for (_, v) in map.iter_mut() {
+                replace_example_placeholders(v, snippet)?;
+            }
+        }
+        serde_yaml_ng::Value::Sequence(seq) => {
+            for v in seq.iter_mut() {
+                replace_example_placeholders(v, snippet)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn read_example_file(file: &str, line: usize, snippet: &Snippet) -> Result<serde_yaml_ng::Value> {
+    let requested = PathBuf::from(file);
+    let mut candidates = Vec::new();
+    if requested.is_absolute() {
+        candidates.push(requested.clone());
+    } else {
+        if let Some(dir) = snippet.file_path.parent() {
+            candidates.push(dir.join(&requested));
+        }
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("."));
+        candidates.push(manifest_dir.join(&requested));
+    }
+
+    let fail = |message: String| Error::ExampleFile {
+        file: snippet.file_path.clone(),
+        line,
+        example: requested.clone(),
+        message,
+    };
+
+    let Some(path) = candidates.into_iter().find(|p| p.is_file()) else {
+        return Err(fail("file not found".to_string()));
+    };
+    let raw = std::fs::read_to_string(&path).map_err(|e| fail(e.to_string()))?;
+
+    match path.extension().and_then(|s| s.to_str()) {
+        Some("json") => {
+            let json: serde_json::Value =
+                serde_json::from_str(&raw).map_err(|e| fail(e.to_string()))?;
+            serde_yaml_ng::to_value(json).map_err(|e| fail(e.to_string()))
+        }
+        Some("yaml") | Some("yml") => {
+            serde_yaml_ng::from_str(&raw).map_err(|e| fail(e.to_string()))
+        }
+        _ => Ok(serde_yaml_ng::Value::String(raw)),
+    }
+}
+
 fn indent(s: &str) -> String {
     s.lines()
         .map(|l| format!("      {}", l))
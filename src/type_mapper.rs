@@ -19,7 +19,7 @@ pub fn map_syn_type_to_openapi(ty: &syn::Type) -> (Value, bool) {
 
                 match ident.as_str() {
                     "bool" => (json!({ "type": "boolean" }), true),
-                    "String" | "str" | "char" => (json!({ "type": "string" }), true),
+                    "String" | "str" | "char" | "PathBuf" => (json!({ "type": "string" }), true),
                     "i8" | "i16" | "i32" | "u8" | "u16" | "u32" => {
                         (json!({ "type": "integer", "format": "int32" }), true)
                     }
@@ -39,6 +39,7 @@ pub fn map_syn_type_to_openapi(ty: &syn::Type) -> (Value, bool) {
                         (json!({ "type": "string", "format": "decimal" }), true)
                     }
                     "ObjectId" => (json!({ "type": "string", "format": "objectid" }), true),
+                    "Bytes" | "BytesMut" => (json!({ "type": "string", "format": "binary" }), true),
                     "Value" => (json!({}), true),
                     "Option" => {
                         if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
@@ -52,6 +53,13 @@ pub fn map_syn_type_to_openapi(ty: &syn::Type) -> (Value, bool) {
                     "Vec" | "LinkedList" | "HashSet" => {
                         if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
                             if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                                // Byte buffers keep their JSON shape but stay recognizable
+                                if is_u8(inner) {
+                                    return (
+                                        json!({ "type": "array", "items": { "type": "integer", "format": "uint8" } }),
+                                        true,
+                                    );
+                                }
                                 let (inner_val, _) = map_syn_type_to_openapi(inner);
                                 return (json!({ "type": "array", "items": inner_val }), true);
                             }
@@ -90,3 +98,7 @@ pub fn map_syn_type_to_openapi(ty: &syn::Type) -> (Value, bool) {
         _ => (json!({ "type": "object" }), true),
     }
 }
+
+fn is_u8(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(p) if p.path.is_ident("u8"))
+}
@@ -1,7 +1,11 @@
 use serde_json::{Value, json};
+use std::collections::HashMap;
 use syn::spanned::Spanned;
 use syn::visit::{self, Visit};
-use syn::{Attribute, Expr, File, ImplItemFn, ItemEnum, ItemFn, ItemMod, ItemStruct, ItemType};
+use syn::{
+    Attribute, Expr, File, ImplItemFn, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait,
+    ItemType, Signature, TraitItemFn, Type,
+};
 
 /// Extracted item type
 #[derive(Debug)]
@@ -32,15 +36,125 @@ pub enum ExtractedItem {
         line: usize,
         operation_id: String,
     },
+    /// Error enum with `@openapi status` variants: JSON map `status -> response`
+    ErrorResponses {
+        name: String,
+        content: String,
+        line: usize,
+    },
 }
 
 #[derive(Default)]
 pub struct OpenApiVisitor {
     pub items: Vec<ExtractedItem>,
     pub current_tags: Vec<String>,
+    /// Scoped `@route-prefix` / `@default-*` lines (parent modules first)
+    pub scoped_directives: Vec<String>,
+    /// Self type of the impl block / trait being visited
+    pub current_type: Option<String>,
+    /// Inline `mod` blocks enclosing the item being visited
+    pub module_path: Vec<String>,
+    /// Resolved `const` / `static` values (see `constants::resolve_constants`)
+    pub constants: HashMap<String, Value>,
 }
 
 impl OpenApiVisitor {
+    // Emits a route DSL block, prefixed with the enclosing scoped directives.
+    fn push_route(&mut self, content: String, line: usize, operation_id: String) {
+        let mut lines = self.scoped_directives.clone();
+        if !self.module_path.is_empty() {
+            lines.push(format!("@infer module {}", self.module_path.join("::")));
+        }
+        lines.push(content);
+        self.items.push(ExtractedItem::RouteDSL {
+            content: lines.join("\n"),
+            line,
+            operation_id,
+        });
+    }
+
+    // Route DSL, webhook or framework attribute route of a function / method.
+    fn visit_handler(
+        &mut self,
+        attrs: &[Attribute],
+        sig: &Signature,
+        line: usize,
+        operation_id: String,
+    ) {
+        let doc_lines = crate::doc_parser::extract_route_doc_comments(attrs);
+
+        // Check for DSL trigger
+        let has_route = doc_lines
+            .iter()
+            .any(|l| l.split_whitespace().next() == Some("@route"));
+        let has_webhook = doc_lines.iter().any(|l| l.trim().starts_with("@webhook"));
+
+        if has_webhook && !has_route {
+            self.push_route(doc_lines.join("\n"), line, operation_id);
+            return;
+        }
+
+        if !has_route {
+            // Framework attributes (#[get("/x")], #[route(...)]) act as an implicit @route
+            // on documented handlers without a manual @openapi block.
+            let attribute_routes = crate::handlers::route_attributes(attrs);
+            let has_docs = doc_lines.iter().any(|l| !l.trim().is_empty());
+            let has_openapi = doc_lines.iter().any(|l| l.contains("@openapi"));
+
+            if attribute_routes.is_empty() || !has_docs || has_openapi {
+                // Legacy Fallback
+                self.check_attributes(attrs, None, line);
+            } else {
+                for (idx, route) in attribute_routes.iter().enumerate() {
+                    let mut lines = crate::handlers::implicit_route_lines(route, sig, &doc_lines);
+                    lines.extend(doc_lines.iter().cloned());
+                    lines.extend(crate::handlers::signature_lines(sig, true));
+                    // `list`, `list_post`, `list_get_2` for a second GET attribute
+                    let ordinal = attribute_routes[..=idx]
+                        .iter()
+                        .filter(|r| r.method == route.method)
+                        .count();
+                    let operation_id = match (idx, ordinal) {
+                        (0, _) => operation_id.clone(),
+                        (_, 1) => format!("{}_{}", operation_id, route.method),
+                        _ => format!("{}_{}_{}", operation_id, route.method, ordinal),
+                    };
+                    self.push_route(lines.join("\n"), line, operation_id);
+                }
+            }
+            return;
+        }
+
+        // Emitting Raw DSL for late binding (signature inference has lower precedence)
+        let mut lines = doc_lines;
+        lines.extend(crate::handlers::signature_lines(sig, false));
+        self.push_route(lines.join("\n"), line, operation_id);
+    }
+
+    // Operation id of a method: `Type_method`.
+    fn method_operation_id(&self, sig: &Signature) -> String {
+        match &self.current_type {
+            Some(ty) => format!("{}_{}", ty, sig.ident),
+            None => sig.ident.to_string(),
+        }
+    }
+
+    // Scoped directives of an impl block / trait; its `@tag` applies to every method.
+    fn enter_type_scope(&mut self, attrs: &[Attribute]) -> usize {
+        let old_len = self.scoped_directives.len();
+        self.scoped_directives.push(SCOPE_MARKER.to_string());
+        for line in crate::doc_parser::extract_doc_comments(attrs) {
+            let trimmed = line.trim();
+            if is_scoped_directive(trimmed) {
+                self.scoped_directives.push(trimmed.to_string());
+            } else if let Some(tags) = trimmed.strip_prefix("@tag ") {
+                self.scoped_directives
+                    .push(format!("@default-tag {}", tags.trim()));
+            }
+        }
+        old_len
+    }
+
     // Process doc attributes on items (structs, fns, types)
     // Updated: No longer accepts generated_content. Strictly for @openapi blocks (Paths/Fragments).
     fn check_attributes(
@@ -249,6 +363,7 @@ impl OpenApiVisitor {
     }
     // Helper to process a single struct field
     fn process_struct_field(
+        &self,
         field: &syn::Field,
         rename_rule: &Option<String>,
     ) -> (String, Value, bool) {
@@ -267,6 +382,20 @@ impl OpenApiVisitor {
             }
         }
 
+        // XML conventions (quick-xml / serde-xml-rs):
+        // `@name` renames map to attributes, `$value` / `$text` to text content.
+        let mut xml = serde_json::Map::new();
+        if let Some(attribute) = field_final_name.strip_prefix('@') {
+            field_final_name = attribute.to_string();
+            xml.insert("attribute".to_string(), json!(true));
+        } else if field_final_name == "$value" || field_final_name == "$text" {
+            field_final_name = match rename_rule {
+                Some(rule) => crate::doc_parser::apply_casing(&default_field_name, rule),
+                None => default_field_name.clone(),
+            };
+            xml.insert("x-text".to_string(), json!(true));
+        }
+
         let (mut field_schema, is_required) = map_syn_type_to_openapi(&field.ty);
 
         // Field Description
@@ -277,7 +406,8 @@ impl OpenApiVisitor {
         }
 
         // Validation Attributes
-        let validation_props = crate::doc_parser::extract_validation(&field.attrs);
+        let validation_props =
+            crate::doc_parser::extract_validation_with_constants(&field.attrs, &self.constants);
         if !validation_props.as_object().unwrap().is_empty() {
             json_merge(&mut field_schema, validation_props);
         }
@@ -290,7 +420,19 @@ impl OpenApiVisitor {
             if trimmed.starts_with("@openapi") {
                 collecting_openapi = true;
                 let rest = trimmed.strip_prefix("@openapi").unwrap().trim();
-                if !rest.is_empty() && !rest.starts_with("rename") {
+                if let Some(spec) = xml_directive(rest) {
+                    xml.extend(parse_xml_directive(spec));
+                } else if rest == "file" {
+                    // Uploaded file (multipart part)
+                    json_merge(
+                        &mut field_schema,
+                        json!({ "type": "string", "format": "binary" }),
+                    );
+                    if let Value::Object(map) = &mut field_schema {
+                        map.remove("$ref");
+                        map.remove("items");
+                    }
+                } else if !rest.is_empty() && !rest.starts_with("rename") {
                     field_openapi_lines.push(rest.to_string());
                 }
             } else if collecting_openapi {
@@ -316,6 +458,21 @@ impl OpenApiVisitor {
             }
         }
 
+        // On arrays `name` names each item; the wrapper (`wrapped`) takes the property name
+        if field_schema.get("items").is_some() {
+            if let Some(name) = xml.remove("name") {
+                json_merge(
+                    &mut field_schema,
+                    json!({ "items": { "xml": { "name": name } } }),
+                );
+            }
+        }
+        if !xml.is_empty() {
+            json_merge(&mut field_schema, json!({ "xml": xml }));
+        }
+
+        apply_example_file(&mut field_schema, &field.attrs);
+
         (field_final_name, field_schema, is_required)
     }
     fn process_enum_variant(
@@ -340,6 +497,116 @@ impl OpenApiVisitor {
     }
 }
 
+impl OpenApiVisitor {
+    /// Responses of an error enum, one per distinct `@openapi status <code> [$Body] ["desc"]`
+    /// of its variants. The body defaults to the variant's data; variants sharing a
+    /// status are combined into a `oneOf`.
+    fn error_responses(&self, i: &ItemEnum) -> Option<String> {
+        let mut statuses: Vec<(String, Vec<String>, Vec<Value>)> = Vec::new();
+        for v in &i.variants {
+            let (_, variant_desc, _, doc_lines, _, _) =
+                crate::doc_parser::extract_naming_and_doc(&v.attrs, &v.ident.to_string());
+            let Some(rest) = doc_lines.iter().find_map(|l| {
+                l.trim()
+                    .strip_prefix("@openapi")
+                    .and_then(|r| r.trim().strip_prefix("status "))
+            }) else {
+                continue;
+            };
+
+            let rest = rest.trim();
+            let (code, residue) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if code.parse::<u16>().is_err() && !code.ends_with("XX") && code != "default" {
+                log::warn!(
+                    "Invalid status '{}' on variant '{}::{}'",
+                    code,
+                    i.ident,
+                    v.ident
+                );
+                continue;
+            }
+            let residue = residue.trim();
+            let (body, desc) = match residue.split_once('"') {
+                Some((body, desc)) => (body.trim(), desc.trim_end_matches('"').to_string()),
+                None => (residue, String::new()),
+            };
+            let desc = if !desc.is_empty() {
+                desc
+            } else if !variant_desc.is_empty() {
+                variant_desc
+            } else {
+                v.ident.to_string()
+            };
+
+            let schema = if let Some(name) = body.strip_prefix('$') {
+                Some(json!({ "$ref": format!("${}", name) }))
+            } else if !body.is_empty() {
+                syn::parse_str::<syn::Type>(body)
+                    .ok()
+                    .map(|ty| map_syn_type_to_openapi(&ty).0)
+            } else {
+                match &v.fields {
+                    syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                        Some(map_syn_type_to_openapi(&fields.unnamed[0].ty).0)
+                    }
+                    syn::Fields::Named(fields) => {
+                        let mut properties = serde_json::Map::new();
+                        let mut required = Vec::new();
+                        for field in &fields.named {
+                            let (name, schema, is_required) =
+                                self.process_struct_field(field, &None);
+                            properties.insert(name.clone(), schema);
+                            if is_required {
+                                required.push(name);
+                            }
+                        }
+                        Some(json!({
+                            "type": "object",
+                            "properties": properties,
+                            "required": required
+                        }))
+                    }
+                    _ => None,
+                }
+            };
+
+            let code = code.to_string();
+            let index = match statuses.iter().position(|(c, _, _)| *c == code) {
+                Some(index) => index,
+                None => {
+                    statuses.push((code, Vec::new(), Vec::new()));
+                    statuses.len() - 1
+                }
+            };
+            let (_, descriptions, schemas) = &mut statuses[index];
+            descriptions.push(desc);
+            if let Some(schema) = schema {
+                if !schemas.contains(&schema) {
+                    schemas.push(schema);
+                }
+            }
+        }
+
+        if statuses.is_empty() {
+            return None;
+        }
+        let mut responses = serde_json::Map::new();
+        for (code, descriptions, mut schemas) in statuses {
+            let mut response = json!({ "description": descriptions.join(" / ") });
+            let schema = match schemas.len() {
+                0 => None,
+                1 => schemas.pop(),
+                _ => Some(json!({ "oneOf": schemas })),
+            };
+            if let Some(schema) = schema {
+                response["content"] = json!({ "application/json": { "schema": schema } });
+            }
+            responses.insert(code, response);
+        }
+        serde_json::to_string(&responses).ok()
+    }
+}
+
 // Helper to wrap content in components/schemas
 fn wrap_in_schema(name: &str, content: &str) -> String {
     let indented = content
@@ -350,6 +617,105 @@ fn wrap_in_schema(name: &str, content: &str) -> String {
     format!("components:\n  schemas:\n    {}:\n{}", name, indented)
 }
 
+/// Directives inherited by every route below a module: `@route-prefix`, `@default-*`
+/// and the response envelopes.
+pub fn is_scoped_directive(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with("@route-prefix ")
+        || trimmed.starts_with("@default-")
+        || trimmed.starts_with("@response-envelope ")
+        || trimmed.starts_with("@error-envelope ")
+}
+
+/// Separates the scoped directives of nested scopes, so that an inner
+/// `@default-security` / `@default-tag` replaces the outer one.
+pub const SCOPE_MARKER: &str = "@default-scope";
+
+/// Scoped directive lines in doc attributes (`//!` or `///`), led by [`SCOPE_MARKER`].
+pub fn scoped_directives(attrs: &[Attribute]) -> Vec<String> {
+    let lines: Vec<String> = crate::doc_parser::extract_doc_comments(attrs)
+        .iter()
+        .filter(|l| is_scoped_directive(l))
+        .map(|l| l.trim().to_string())
+        .collect();
+    if lines.is_empty() {
+        return lines;
+    }
+    std::iter::once(SCOPE_MARKER.to_string())
+        .chain(lines)
+        .collect()
+}
+
+/// Key of the placeholder that the scanner replaces with the contents of an example file.
+pub const EXAMPLE_FILE_MARKER: &str = "x-oas-forge-example-file";
+/// Source line of the directive naming the example file, next to [`EXAMPLE_FILE_MARKER`].
+pub const EXAMPLE_LINE_MARKER: &str = "x-oas-forge-example-line";
+
+// Helper to turn `example-file: path` into a JSON Schema `examples` entry.
+// The file itself is loaded by the scanner, which knows the source file.
+fn apply_example_file(schema: &mut Value, attrs: &[Attribute]) {
+    if let Some(map) = schema.as_object_mut() {
+        if let Some(Value::String(path)) = map.remove("example-file") {
+            let mut placeholder = json!({ EXAMPLE_FILE_MARKER: path });
+            let directive = attrs.iter().find(|attr| {
+                crate::doc_parser::extract_doc_comments(std::slice::from_ref(attr))
+                    .iter()
+                    .any(|line| line.contains("example-file"))
+            });
+            if let Some(attr) = directive {
+                placeholder[EXAMPLE_LINE_MARKER] = json!(attr.span().start().line);
+            }
+            match map.get_mut("examples") {
+                Some(Value::Array(examples)) => examples.push(placeholder),
+                _ => {
+                    map.insert("examples".to_string(), json!([placeholder]));
+                }
+            }
+        }
+    }
+}
+
+// Returns the arguments of an `xml` directive (`@openapi xml name=foo attribute`).
+fn xml_directive(rest: &str) -> Option<&str> {
+    if rest == "xml" {
+        return Some("");
+    }
+    rest.strip_prefix("xml ").map(str::trim)
+}
+
+// Parses `name=foo attribute wrapped namespace=https://... prefix=p` into an OpenAPI XML object.
+// Flags accept an explicit `=true` / `=false`.
+fn parse_xml_directive(spec: &str) -> serde_json::Map<String, Value> {
+    let mut xml = serde_json::Map::new();
+    for token in spec.split_whitespace() {
+        let (key, value) = match token.split_once('=') {
+            Some((k, v)) => (k, Some(v.trim_matches('"'))),
+            None => (token, None),
+        };
+        match key {
+            "name" | "namespace" | "prefix" => {
+                if let Some(v) = value {
+                    xml.insert(key.to_string(), json!(v));
+                } else {
+                    log::warn!("XML directive '{}' requires a value", key);
+                }
+            }
+            "attribute" | "wrapped" => {
+                let flag = value.map(|v| v != "false").unwrap_or(true);
+                xml.insert(key.to_string(), json!(flag));
+            }
+            "text" => {
+                xml.insert(
+                    "x-text".to_string(),
+                    json!(value.map(|v| v != "false").unwrap_or(true)),
+                );
+            }
+            _ => log::warn!("Unknown XML directive token '{}'", token),
+        }
+    }
+    xml
+}
+
 pub use crate::type_mapper::map_syn_type_to_openapi;
 
 // Deep Merge Helper for JSON Values
@@ -366,6 +732,9 @@ pub fn json_merge(a: &mut Value, b: Value) {
 
 impl<'ast> Visit<'ast> for OpenApiVisitor {
     fn visit_file(&mut self, i: &'ast File) {
+        // File-level `//! @route-prefix` / `@default-*` apply to every route of the file
+        self.scoped_directives.extend(scoped_directives(&i.attrs));
+
         // State machine for file-level doc blocks
         let mut current_block_type: Option<String> = None;
         let mut current_block_lines = Vec::new();
@@ -380,7 +749,9 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                             let raw_line = lit_str.value();
                             let trimmed = raw_line.trim();
 
-                            if trimmed.starts_with("@openapi-type") {
+                            if is_scoped_directive(trimmed) {
+                                continue;
+                            } else if trimmed.starts_with("@openapi-type") {
                                 // Flush previous if exists
                                 if !current_block_lines.is_empty() {
                                     let body = current_block_lines.join("\n");
@@ -424,7 +795,7 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                                 current_block_type = None;
                                 start_line = attr.span().start().line;
                                 current_block_lines.push(raw_line); // preserve header
-                            } else if trimmed.starts_with("@route") {
+                            } else if crate::dsl::is_operation_line(trimmed) {
                                 // Flush previous unless it was just general docs
                                 if !current_block_lines.is_empty() && current_block_type.is_some() {
                                     // If we were building a type, flush it
@@ -448,6 +819,8 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                             } else if !current_block_lines.is_empty()
                                 || current_block_type.is_some()
                             {
+                                current_block_lines
+                                    .extend(crate::doc_parser::example_line_hint(attr, &raw_line));
                                 current_block_lines.push(raw_line);
                             }
                         }
@@ -466,12 +839,12 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                         });
                     } else {
                         // Check if it's a virtual route
-                        if body.contains("@route") {
-                            self.items.push(ExtractedItem::RouteDSL {
-                                content: body,
-                                line: start_line,
-                                operation_id: format!("virtual_route_{}", start_line),
-                            });
+                        if body.lines().any(crate::dsl::is_operation_line) {
+                            self.push_route(
+                                body,
+                                start_line,
+                                format!("{}{}", crate::dsl::VIRTUAL_ROUTE_PREFIX, start_line),
+                            );
                         } else {
                             self.parse_doc_block(&body, None, start_line);
                         }
@@ -493,12 +866,12 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                 });
             } else {
                 // Check if it's a virtual route
-                if body.contains("@route") {
-                    self.items.push(ExtractedItem::RouteDSL {
-                        content: body,
-                        line: start_line,
-                        operation_id: format!("virtual_route_{}", start_line),
-                    });
+                if body.lines().any(crate::dsl::is_operation_line) {
+                    self.push_route(
+                        body,
+                        start_line,
+                        format!("{}{}", crate::dsl::VIRTUAL_ROUTE_PREFIX, start_line),
+                    );
                 } else {
                     self.parse_doc_block(&body, None, start_line);
                 }
@@ -509,37 +882,12 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
     }
 
     fn visit_item_fn(&mut self, i: &'ast ItemFn) {
-        let mut doc_lines = Vec::new();
-        for attr in &i.attrs {
-            if attr.path().is_ident("doc") {
-                if let syn::Meta::NameValue(meta) = &attr.meta {
-                    if let Expr::Lit(expr_lit) = &meta.value {
-                        if let syn::Lit::Str(lit_str) = &expr_lit.lit {
-                            doc_lines.push(lit_str.value());
-                        }
-                    }
-                }
-            }
-        }
-
-        // Check for DSL trigger
-        let has_route = doc_lines.iter().any(|l| l.trim().starts_with("@route"));
-
-        if !has_route {
-            // Legacy Fallback
-            self.check_attributes(&i.attrs, None, i.span().start().line);
-            visit::visit_item_fn(self, i);
-            return;
-        }
-
-        // Emitting Raw DSL for late binding
-        let content = doc_lines.join("\n");
-        self.items.push(ExtractedItem::RouteDSL {
-            content,
-            line: i.span().start().line,
-            operation_id: i.sig.ident.to_string(),
-        });
-
+        self.visit_handler(
+            &i.attrs,
+            &i.sig,
+            i.span().start().line,
+            i.sig.ident.to_string(),
+        );
         visit::visit_item_fn(self, i);
     }
 
@@ -595,6 +943,8 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
             }
         }
 
+        apply_example_file(&mut schema, &i.attrs);
+
         if let Ok(generated) = serde_yaml_ng::to_string(&schema) {
             let trimmed = generated.trim_start_matches("---\n").to_string();
             let wrapped = wrap_in_schema(&ident, &trimmed);
@@ -628,7 +978,7 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
             for field in &fields.named {
                 has_fields = true;
                 let (field_final_name, field_schema, is_required) =
-                    Self::process_struct_field(field, &rename_rule);
+                    self.process_struct_field(field, &rename_rule);
 
                 properties.insert(field_final_name.clone(), field_schema);
                 if is_required {
@@ -663,6 +1013,8 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
         let mut openapi_lines = Vec::new();
         let mut collecting_openapi = false;
         let mut blueprint_params: Option<Vec<String>> = None;
+        let mut extends: Option<(String, Option<String>)> = None;
+        let mut xml = serde_json::Map::new();
 
         for line in &doc_lines {
             let trimmed = line.trim();
@@ -671,7 +1023,19 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                 let rest = trimmed.strip_prefix("@openapi").unwrap().trim();
 
                 if !rest.is_empty() && !rest.starts_with("rename") && !rest.starts_with("-type") {
-                    if rest.contains('<') {
+                    if let Some(spec) = xml_directive(rest) {
+                        // XML representation: @openapi xml name=Item wrapped ...
+                        xml.extend(parse_xml_directive(spec));
+                    } else if let Some(spec) = rest.strip_prefix("extends ") {
+                        // Inheritance: @openapi extends $Base [mapping=value]
+                        let mut tokens = spec.split_whitespace();
+                        if let Some(base) = tokens.next() {
+                            let mapping = tokens
+                                .find_map(|t| t.strip_prefix("mapping="))
+                                .map(|m| m.trim_matches('"').to_string());
+                            extends = Some((base.trim_start_matches('$').to_string(), mapping));
+                        }
+                    } else if rest.contains('<') {
                         // Blueprint detection
                         if let Some(start) = rest.find('<') {
                             if let Some(end) = rest.rfind('>') {
@@ -699,6 +1063,26 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
             }
         }
 
+        if !xml.is_empty() {
+            json_merge(&mut schema, json!({ "xml": xml }));
+        }
+
+        // Inheritance: compose the base and the own properties via allOf
+        if let Some((base, mapping)) = extends {
+            let description = schema.as_object_mut().and_then(|m| m.remove("description"));
+            let mut composed = json!({
+                "allOf": [{ "$ref": format!("${}", base) }, schema],
+                "x-oas-forge-extends": {
+                    "base": base,
+                    "mapping": mapping.unwrap_or_else(|| final_name.clone())
+                }
+            });
+            if let Some(desc) = description {
+                composed["description"] = desc;
+            }
+            schema = composed;
+        }
+
         if !openapi_lines.is_empty() {
             let override_yaml = openapi_lines.join("\n");
             match serde_yaml_ng::from_str::<Value>(&override_yaml) {
@@ -717,6 +1101,8 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
             }
         }
 
+        apply_example_file(&mut schema, &i.attrs);
+
         // Final Serialize
         match serde_yaml_ng::to_string(&schema) {
             Ok(generated) => {
@@ -756,6 +1142,15 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
         let (final_name, enum_desc, rename_rule, doc_lines, serde_tag, serde_content) =
             crate::doc_parser::extract_naming_and_doc(&i.attrs, &default_name);
 
+        // Error enums (`@openapi status 404` on variants), referenced via `@errors`
+        if let Some(content) = self.error_responses(i) {
+            self.items.push(ExtractedItem::ErrorResponses {
+                name: default_name.clone(),
+                content,
+                line: i.span().start().line,
+            });
+        }
+
         // Safety: Explicit export only
         if !doc_lines.iter().any(|l| l.contains("@openapi")) {
             visit::visit_item_enum(self, i);
@@ -815,7 +1210,7 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
 
                     for field in &fields.named {
                         let (f_name, f_schema, f_req) =
-                            Self::process_struct_field(field, &rename_rule);
+                            self.process_struct_field(field, &rename_rule);
                         inner_props.insert(f_name.clone(), f_schema);
                         if f_req {
                             inner_req.push(f_name);
@@ -834,7 +1229,10 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
                             crate::type_mapper::map_syn_type_to_openapi(&field.ty);
 
                         // Apply validation attributes
-                        let validation = crate::doc_parser::extract_validation(&field.attrs);
+                        let validation = crate::doc_parser::extract_validation_with_constants(
+                            &field.attrs,
+                            &self.constants,
+                        );
                         if validation.is_object() {
                             crate::visitor::json_merge(&mut schema, validation);
                         }
@@ -1005,6 +1403,8 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
             }
         }
 
+        apply_example_file(&mut schema, &i.attrs);
+
         // Only emit if we have variants OR overrides
         if !variants.is_empty() || !openapi_lines.is_empty() {
             if let Ok(generated) = serde_yaml_ng::to_string(&schema) {
@@ -1055,32 +1455,140 @@ impl<'ast> Visit<'ast> for OpenApiVisitor {
             }
         }
 
+        // Module-level webhooks: one operation per `@webhook` block
+        let mut webhook_blocks: Vec<Vec<String>> = Vec::new();
+        let mut in_webhook = false;
+        for attr in &i.attrs {
+            if attr.path().is_ident("doc") {
+                if let syn::Meta::NameValue(meta) = &attr.meta {
+                    if let Expr::Lit(expr_lit) = &meta.value {
+                        if let syn::Lit::Str(lit_str) = &expr_lit.lit {
+                            let val = lit_str.value();
+                            let trimmed = val.trim();
+                            if trimmed.starts_with("@webhook") {
+                                webhook_blocks.push(Vec::new());
+                                in_webhook = true;
+                            } else if trimmed.starts_with("@openapi") {
+                                in_webhook = false;
+                            }
+                            if let (true, Some(block)) = (in_webhook, webhook_blocks.last_mut()) {
+                                block.extend(crate::doc_parser::example_line_hint(attr, &val));
+                                block.push(val);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for block in webhook_blocks {
+            let name = block[0].split_whitespace().nth(1).unwrap_or("webhook");
+            self.items.push(ExtractedItem::RouteDSL {
+                operation_id: name.to_string(),
+                content: block.join("\n"),
+                line: i.span().start().line,
+            });
+        }
+
         let old_len = self.current_tags.len();
         self.current_tags.extend(found_tags);
+        let old_scoped = self.scoped_directives.len();
+        self.scoped_directives.extend(scoped_directives(&i.attrs));
+        self.module_path.push(i.ident.to_string());
 
         self.check_attributes(&i.attrs, None, i.span().start().line);
         visit::visit_item_mod(self, i);
 
+        self.module_path.pop();
         self.current_tags.truncate(old_len);
+        self.scoped_directives.truncate(old_scoped);
+    }
+
+    fn visit_item_impl(&mut self, i: &'ast ItemImpl) {
+        let old_scoped = self.enter_type_scope(&i.attrs);
+        let old_type = self.current_type.take();
+        if let Type::Path(type_path) = &*i.self_ty {
+            self.current_type = type_path.path.segments.last().map(|s| s.ident.to_string());
+        }
+
+        visit::visit_item_impl(self, i);
+
+        self.current_type = old_type;
+        self.scoped_directives.truncate(old_scoped);
     }
 
     fn visit_impl_item_fn(&mut self, i: &'ast ImplItemFn) {
-        self.check_attributes(&i.attrs, None, i.span().start().line);
+        let operation_id = self.method_operation_id(&i.sig);
+        self.visit_handler(&i.attrs, &i.sig, i.span().start().line, operation_id);
         visit::visit_impl_item_fn(self, i);
     }
+
+    fn visit_item_trait(&mut self, i: &'ast ItemTrait) {
+        let old_scoped = self.enter_type_scope(&i.attrs);
+        let old_type = self.current_type.replace(i.ident.to_string());
+
+        visit::visit_item_trait(self, i);
+
+        self.current_type = old_type;
+        self.scoped_directives.truncate(old_scoped);
+    }
+
+    fn visit_trait_item_fn(&mut self, i: &'ast TraitItemFn) {
+        let operation_id = self.method_operation_id(&i.sig);
+        self.visit_handler(&i.attrs, &i.sig, i.span().start().line, operation_id);
+        visit::visit_trait_item_fn(self, i);
+    }
 }
 
+/// Extracts items from a single file, resolving the constants it declares itself.
 pub fn extract_from_file(path: std::path::PathBuf) -> crate::error::Result<Vec<ExtractedItem>> {
+    let constants = crate::constants::resolve_constants(crate::constants::index_file(&path)?);
+    extract_from_file_with_constants(path, &constants)
+}
+
+/// Extracts items from a file, substituting `{{const NAME}}` placeholders
+/// and resolving constant references in validator arguments.
+pub fn extract_from_file_with_constants(
+    path: std::path::PathBuf,
+    constants: &HashMap<String, Value>,
+) -> crate::error::Result<Vec<ExtractedItem>> {
+    extract_from_file_with_scope(path, constants, &[])
+}
+
+/// Same as [`extract_from_file_with_constants`], starting from the scoped
+/// directives inherited from parent modules declared in other files.
+pub fn extract_from_file_with_scope(
+    path: std::path::PathBuf,
+    constants: &HashMap<String, Value>,
+    scoped_directives: &[String],
+) -> crate::error::Result<Vec<ExtractedItem>> {
     let content = std::fs::read_to_string(&path)?;
-    let parsed_file = syn::parse_file(&content).map_err(|e| crate::error::Error::Parse {
+    let mut parsed_file = syn::parse_file(&content).map_err(|e| crate::error::Error::Parse {
         file: path.clone(),
         source: e,
     })?;
+    crate::constants::substitute_doc_constants(&mut parsed_file, constants);
 
-    let mut visitor = OpenApiVisitor::default();
-    visitor.visit_file(&parsed_file);
+    Ok(extract_from_parsed(
+        &parsed_file,
+        constants,
+        scoped_directives,
+    ))
+}
 
-    Ok(visitor.items)
+/// Extracts items from an already parsed file whose doc comments had their
+/// `{{const NAME}}` placeholders substituted.
+pub fn extract_from_parsed(
+    file: &syn::File,
+    constants: &HashMap<String, Value>,
+    scoped_directives: &[String],
+) -> Vec<ExtractedItem> {
+    let mut visitor = OpenApiVisitor {
+        constants: constants.clone(),
+        scoped_directives: scoped_directives.to_vec(),
+        ..Default::default()
+    };
+    visitor.visit_file(file);
+    visitor.items
 }
 
 #[cfg(test)]
@@ -0,0 +1,27 @@
+// Shared by several test crates; each one uses a subset of the helpers.
+#![allow(dead_code)]
+
+use oas_forge::error::Result;
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::{ScanOptions, scan_directories_with};
+use serde_json::Value;
+use tempfile::tempdir;
+
+/// Scans `source` as `src/api.rs` of a temporary crate and merges the snippets.
+pub fn try_scan(source: &str, options: &ScanOptions) -> Result<Value> {
+    let dir = tempdir()?;
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src)?;
+    std::fs::write(src.join("api.rs"), source)?;
+
+    let (snippets, _) = scan_directories_with(&[src], &[], options)?;
+    Ok(serde_json::to_value(merge_openapi(snippets)?)?)
+}
+
+pub fn scan_with(source: &str, options: &ScanOptions) -> Value {
+    try_scan(source, options).unwrap()
+}
+
+pub fn scan(source: &str) -> Value {
+    scan_with(source, &ScanOptions::default())
+}
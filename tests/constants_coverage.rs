@@ -0,0 +1,159 @@
+use oas_forge::constants::{eval_const_expr, resolve_constants, substitute_constants};
+use oas_forge::scanner::scan_directories;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use tempfile::tempdir;
+
+#[test]
+fn test_eval_literals_and_arithmetic() {
+    let constants = HashMap::from([("BASE".to_string(), json!(10))]);
+    let eval = |code: &str| eval_const_expr(&syn::parse_str(code).unwrap(), &constants);
+
+    assert_eq!(eval("100"), Some(json!(100)));
+    assert_eq!(eval("-5"), Some(json!(-5)));
+    assert_eq!(eval("10 * 10 + 1"), Some(json!(101)));
+    assert_eq!(eval("(BASE - 2) / 4"), Some(json!(2)));
+    assert_eq!(eval("BASE as f64 / 4.0"), Some(json!(2.5)));
+    assert_eq!(eval("\"v1\""), Some(json!("v1")));
+    assert_eq!(eval("compute()"), None);
+    assert_eq!(eval("UNKNOWN + 1"), None);
+}
+
+#[test]
+fn test_resolve_out_of_order() {
+    let pending = vec![
+        (
+            "MAX_PAGE_SIZE".to_string(),
+            syn::parse_str("DEFAULT_PAGE_SIZE * 4").unwrap(),
+        ),
+        (
+            "DEFAULT_PAGE_SIZE".to_string(),
+            syn::parse_str("25").unwrap(),
+        ),
+    ];
+    let resolved = resolve_constants(pending);
+    assert_eq!(resolved["MAX_PAGE_SIZE"], json!(100));
+
+    let text = substitute_constants("maximum: {{const MAX_PAGE_SIZE}}", &resolved);
+    assert_eq!(text, "maximum: 100");
+    let untouched = substitute_constants("maximum: {{const NOPE}}", &resolved);
+    assert_eq!(untouched, "maximum: {{const NOPE}}");
+}
+
+#[test]
+fn test_first_declaration_wins_and_paths_are_qualified() {
+    let parse = |code: &str| syn::parse_str::<syn::Expr>(code).unwrap();
+    let pending = vec![
+        // Declared first, but only resolvable once `a::BASE` is known
+        ("a::LIMIT".to_string(), parse("BASE * 5")),
+        ("a::BASE".to_string(), parse("20")),
+        ("b::LIMIT".to_string(), parse("50")),
+        ("b::BASE".to_string(), parse("1")),
+        ("b::DOUBLE".to_string(), parse("BASE * 2")),
+    ];
+    let resolved = resolve_constants(pending);
+
+    assert_eq!(resolved["LIMIT"], json!(100));
+    // Bare names inside a module prefer that module
+    assert_eq!(resolved["crate::b::DOUBLE"], json!(2));
+
+    let eval = |code: &str| eval_const_expr(&parse(code), &resolved);
+    assert_eq!(eval("a::LIMIT"), Some(json!(100)));
+    assert_eq!(eval("b::LIMIT"), Some(json!(50)));
+    assert_eq!(eval("crate::b::LIMIT"), Some(json!(50)));
+    assert_eq!(eval("c::LIMIT"), None);
+
+    let text = substitute_constants("{{const LIMIT}} {{const b::LIMIT}}", &resolved);
+    assert_eq!(text, "100 50");
+}
+
+#[test]
+fn test_constants_substituted_at_scan_time() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("limits.rs"),
+        r#"
+        pub const MAX_PAGE_SIZE: u32 = 10 * 10;
+        pub static MIN_NAME: usize = 3;
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("models.rs"),
+        r#"
+        /// @openapi
+        pub struct Query {
+            /// @openapi maximum: {{const MAX_PAGE_SIZE}}
+            pub limit: u32,
+            #[validate(length(min = MIN_NAME, max = MAX_PAGE_SIZE))]
+            pub name: String,
+            #[validate(range(min = 1, max = crate::limits::MAX_PAGE_SIZE))]
+            pub page: u32,
+            #[serde(rename = "{{const MAX_PAGE_SIZE}}")]
+            pub raw: String,
+        }
+
+        /// @route GET /items
+        /// @query-param limit: u32 example={{const MAX_PAGE_SIZE}} "Page size"
+        fn list_items() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+
+    let schema = snippets
+        .iter()
+        .find(|s| s.content.contains("Query:"))
+        .expect("Query schema");
+    let root: Value = serde_yaml_ng::from_str(&schema.content).unwrap();
+    let props = &root["components"]["schemas"]["Query"]["properties"];
+    assert_eq!(props["limit"]["maximum"], 100);
+    assert_eq!(props["name"]["minLength"], 3);
+    assert_eq!(props["name"]["maxLength"], 100);
+    assert_eq!(props["page"]["maximum"], 100);
+    // Only doc comments are substituted, not code
+    assert!(props.get("{{const MAX_PAGE_SIZE}}").is_some());
+
+    let route = snippets
+        .iter()
+        .find(|s| s.operation_id.as_deref() == Some("list_items"))
+        .expect("route");
+    let root: Value = serde_yaml_ng::from_str(&route.content).unwrap();
+    assert_eq!(
+        root["paths"]["/items"]["get"]["parameters"][0]["example"],
+        100
+    );
+}
+
+#[test]
+fn test_extract_from_file_resolves_local_constants() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("models.rs");
+    std::fs::write(
+        &path,
+        r#"
+        /// @openapi
+        pub struct Page {
+            #[validate(range(max = LIMIT))]
+            pub size: u32,
+        }
+
+        const LIMIT: u32 = 50;
+        "#,
+    )
+    .unwrap();
+
+    let items = oas_forge::visitor::extract_from_file(path).unwrap();
+    let Some(oas_forge::visitor::ExtractedItem::Schema { content, .. }) = items.first() else {
+        panic!("Expected schema");
+    };
+    let root: Value = serde_yaml_ng::from_str(content).unwrap();
+    assert_eq!(
+        root["components"]["schemas"]["Page"]["properties"]["size"]["maximum"],
+        50
+    );
+}
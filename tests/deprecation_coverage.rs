@@ -0,0 +1,133 @@
+use oas_forge::Generator;
+use oas_forge::deprecation::{deprecated_operations, http_date, parse_date};
+use oas_forge::dsl::parse_route_dsl;
+use oas_forge::error::Error;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_deprecation_lifecycle() {
+    let lines = vec![
+        "@route GET /users/{id: u32}".to_string(),
+        "@deprecated since=2026-01-01 sunset=2026-12-31 replacement=get_user_v2".to_string(),
+        "@return 200: $User".to_string(),
+        "@return 404: \"Not found\"".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "get_user").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let op = &root["paths"]["/users/{id}"]["get"];
+    assert_eq!(op["deprecated"], true);
+    assert_eq!(op["x-deprecated-since"], "2026-01-01");
+    assert_eq!(op["x-sunset"], "2026-12-31");
+    assert_eq!(op["x-replaced-by"], "get_user_v2");
+
+    for code in ["200", "404"] {
+        let headers = &op["responses"][code]["headers"];
+        assert_eq!(headers["Deprecation"]["example"], "@1767225600");
+        assert_eq!(
+            headers["Sunset"]["example"],
+            "Thu, 31 Dec 2026 00:00:00 GMT"
+        );
+    }
+
+    // Plain @deprecated only sets the flag; invalid dates are skipped
+    let lines = vec![
+        "@route GET /old".to_string(),
+        "@deprecated since=2026-13-01".to_string(),
+    ];
+    let root: Value = serde_yaml_ng::from_str(&parse_route_dsl(&lines, "old").unwrap()).unwrap();
+    let op = &root["paths"]["/old"]["get"];
+    assert_eq!(op["deprecated"], true);
+    assert!(op["responses"]["200"].get("headers").is_none());
+
+    assert_eq!(parse_date("2026-02-30"), None);
+    assert_eq!(
+        http_date(parse_date("1970-01-01").unwrap()),
+        "Thu, 01 Jan 1970 00:00:00 GMT"
+    );
+    assert_eq!(
+        http_date(parse_date("2024-02-29").unwrap()),
+        "Thu, 29 Feb 2024 00:00:00 GMT"
+    );
+}
+
+fn write_api(src: &std::path::Path) {
+    std::fs::create_dir_all(src).unwrap();
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        //! @openapi
+        //! openapi: 3.1.0
+        //! info:
+        //!   title: Lifecycle
+        //!   version: 1.0.0
+
+        /// Old users
+        /// @route GET /v0/users
+        /// @deprecated since=2020-01-01 sunset=2021-01-01 replacement=list_users
+        fn list_users_v0() {}
+
+        /// Users
+        /// @route GET /v1/users
+        /// @deprecated sunset=2999-12-31
+        /// @return 200: "Users"
+        fn list_users_v1() {}
+
+        /// Users
+        /// @route GET /users
+        fn list_users() {}
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_deprecation_report_and_fail_on_sunset() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    write_api(&src);
+    let output = dir.path().join("openapi.json");
+
+    let result = Generator::new()
+        .input(&src)
+        .output(&output)
+        .fail_on_sunset(true)
+        .generate();
+    match result {
+        Err(Error::SunsetPassed { operations }) => {
+            assert_eq!(
+                operations,
+                vec![
+                    "GET /v0/users (list_users_v0), since 2020-01-01, sunset 2021-01-01, replaced by list_users"
+                ]
+            );
+        }
+        other => panic!("Expected SunsetPassed, got {:?}", other),
+    }
+
+    // The spec is still written
+    let spec: serde_yaml_ng::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    let deprecated = deprecated_operations(&spec);
+    assert_eq!(deprecated.len(), 2);
+    let v1 = deprecated
+        .iter()
+        .find(|d| d.operation == "GET /v1/users")
+        .unwrap();
+    assert!(!v1.sunset_passed("2026-10-18"));
+    assert!(v1.sunset_passed("2999-12-31"));
+    assert_eq!(
+        serde_json::to_value(&spec).unwrap()["paths"]["/v1/users"]["get"]["responses"]["200"]["headers"]
+            ["Deprecation"],
+        json!({ "description": "The operation is deprecated", "schema": { "type": "string" } })
+    );
+
+    assert!(
+        Generator::new()
+            .input(&src)
+            .output(&output)
+            .deprecation_report(true)
+            .generate()
+            .is_ok()
+    );
+}
@@ -1,5 +1,5 @@
 use oas_forge::dsl::parse_route_dsl;
-use serde_json::Value;
+use serde_json::{Value, json};
 
 #[test]
 fn test_params_primitive() {
@@ -90,6 +90,71 @@ fn test_params_attrs() {
     assert_eq!(p["description"], "Search Term");
 }
 
+#[test]
+fn test_params_extended_grammar() {
+    let lines = vec![
+        "@route GET /test".to_string(),
+        "@query-param page: u32 min=1 max=100 default=1 \"Page\"".to_string(),
+        "@query-param ratio: f64 min=0.5 example=1.5".to_string(),
+        "@query-param name: String min-length=2 max-length=32 pattern=\"^[a-z ]+$\"".to_string(),
+        "@query-param sort: String enum=asc|desc default=asc allow-empty".to_string(),
+        "@query-param ids: Vec<u32> style=pipeDelimited explode=false max-length=10 enum=1|2"
+            .to_string(),
+        "@query-param q: allow-reserved".to_string(),
+        "@query-param filter: Filter content=application/json".to_string(),
+        "@query-param bad: u32 min=abc style=weird explode=maybe bogus default=1".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "op").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let params = root["paths"]["/test"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let param = |name: &str| params.iter().find(|p| p["name"] == name).unwrap().clone();
+
+    let page = param("page");
+    assert_eq!(page["schema"]["minimum"], 1);
+    assert_eq!(page["schema"]["maximum"], 100);
+    assert_eq!(page["schema"]["default"], 1);
+    assert_eq!(page["description"], "Page");
+
+    assert_eq!(param("ratio")["schema"]["minimum"], 0.5);
+    assert_eq!(param("ratio")["example"], 1.5);
+
+    let name = param("name");
+    assert_eq!(name["schema"]["minLength"], 2);
+    assert_eq!(name["schema"]["maxLength"], 32);
+    assert_eq!(name["schema"]["pattern"], "^[a-z ]+$");
+
+    let sort = param("sort");
+    assert_eq!(sort["schema"]["enum"], json!(["asc", "desc"]));
+    assert_eq!(sort["schema"]["default"], "asc");
+    assert_eq!(sort["allowEmptyValue"], true);
+
+    let ids = param("ids");
+    assert_eq!(ids["style"], "pipeDelimited");
+    assert_eq!(ids["explode"], false);
+    assert_eq!(ids["schema"]["maxItems"], 10);
+    assert_eq!(ids["schema"]["items"]["enum"], json!([1, 2]));
+
+    assert_eq!(param("q")["allowReserved"], true);
+    assert_eq!(param("q")["schema"]["type"], "string");
+
+    let filter = param("filter");
+    assert!(filter.get("schema").is_none());
+    assert_eq!(
+        filter["content"]["application/json"]["schema"]["$ref"],
+        "$Filter"
+    );
+
+    // Malformed tokens are reported and skipped
+    let bad = param("bad");
+    assert!(bad["schema"].get("minimum").is_none());
+    assert!(bad.get("style").is_none());
+    assert!(bad.get("explode").is_none());
+    assert_eq!(bad["schema"]["default"], 1);
+}
+
 #[test]
 fn test_inline_path_params() {
     let lines = vec!["@route GET /users/{id: u32 \"User ID\"}".to_string()];
@@ -271,3 +336,151 @@ fn test_return_inline_json_schema() {
     assert_eq!(schema["type"], "array");
     assert_eq!(schema["items"]["$ref"], "#/components/schemas/Codec");
 }
+
+#[test]
+fn test_return_media_types_ranges_and_binary() {
+    let lines = vec![
+        "@route GET /reports".to_string(),
+        "@return 200: $Report \"Report\" application/json, text/csv".to_string(),
+        "@return 200: file \"PDF\" application/pdf".to_string(),
+        "@return 202: String \"Queued\" text/plain".to_string(),
+        "@return 4xx: $Error \"Client error\"".to_string(),
+        "@return default: HashMap<String, String> \"Unexpected\"".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "op").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let responses = &root["paths"]["/reports"]["get"]["responses"];
+
+    let ok = &responses["200"];
+    assert_eq!(ok["description"], "Report");
+    assert_eq!(
+        ok["content"]["text/csv"]["schema"]["$ref"],
+        "#/components/schemas/Report"
+    );
+    assert!(ok["content"].get("application/json").is_some());
+    assert_eq!(
+        ok["content"]["application/pdf"]["schema"],
+        serde_json::json!({ "type": "string", "format": "binary" })
+    );
+
+    assert_eq!(
+        responses["202"]["content"]["text/plain"]["schema"]["type"],
+        "string"
+    );
+    assert_eq!(responses["4XX"]["description"], "Client error");
+    assert_eq!(
+        responses["default"]["content"]["application/json"]["schema"]["type"],
+        "object"
+    );
+}
+
+#[test]
+fn test_response_headers_and_links() {
+    let lines = vec![
+        "@route POST /users".to_string(),
+        "@response-header 201 Location: String \"URL of created resource\"".to_string(),
+        "@response-header 201 X-RateLimit-Remaining: Option<u32> deprecated".to_string(),
+        "@link 201 GetUser operationId=get_user id=$response.body#/id".to_string(),
+        "@return 201: $User \"Created\"".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "create_user").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let created = &root["paths"]["/users"]["post"]["responses"]["201"];
+
+    assert_eq!(created["description"], "Created");
+    assert!(created["content"].get("application/json").is_some());
+
+    let location = &created["headers"]["Location"];
+    assert_eq!(location["description"], "URL of created resource");
+    assert_eq!(location["schema"]["type"], "string");
+    assert!(location.get("name").is_none());
+    let remaining = &created["headers"]["X-RateLimit-Remaining"];
+    assert_eq!(remaining["required"], false);
+    assert_eq!(remaining["deprecated"], true);
+
+    let link = &created["links"]["GetUser"];
+    assert_eq!(link["operationId"], "get_user");
+    assert_eq!(link["parameters"]["id"], "$response.body#/id");
+}
+
+#[test]
+fn test_operation_metadata_directives() {
+    let lines = vec![
+        " Lists users".to_string(),
+        " with paging".to_string(),
+        "@route GET /users".to_string(),
+        "@operation-id listUsers".to_string(),
+        "@summary List all users".to_string(),
+        "@deprecated".to_string(),
+        "@external-docs https://docs.example.com/users \"User guide\"".to_string(),
+        "@server https://eu.example.com \"EU region\"".to_string(),
+        "@server https://us.example.com".to_string(),
+        "@x-rate-limit { limit: 100, window: 60 }".to_string(),
+        "@x-internal".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "list_users").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let op = &root["paths"]["/users"]["get"];
+
+    assert_eq!(op["operationId"], "listUsers");
+    assert_eq!(op["summary"], "List all users");
+    assert_eq!(op["description"], "Lists users\nwith paging");
+    assert_eq!(op["deprecated"], true);
+    assert_eq!(op["externalDocs"]["url"], "https://docs.example.com/users");
+    assert_eq!(op["externalDocs"]["description"], "User guide");
+    assert_eq!(op["servers"][0]["description"], "EU region");
+    assert_eq!(op["servers"][1]["url"], "https://us.example.com");
+    assert_eq!(op["x-rate-limit"]["limit"], 100);
+    assert_eq!(op["x-internal"], true);
+
+    let lines = vec![
+        "@route GET /ping".to_string(),
+        "@description Liveness probe".to_string(),
+        "Ping".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "ping").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let op = &root["paths"]["/ping"]["get"];
+    assert_eq!(op["summary"], "Ping");
+    assert_eq!(op["description"], "Liveness probe");
+    assert!(op.get("deprecated").is_none());
+}
+
+#[test]
+fn test_multiple_routes_per_handler() {
+    let lines = vec![
+        "Fetch a user".to_string(),
+        "@route GET|HEAD /users/{id: u32}".to_string(),
+        "@route GET /legacy/users/{id: u32}".to_string(),
+        "@return 200: $User".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "get_user").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let paths = &root["paths"];
+
+    let get = &paths["/users/{id}"]["get"];
+    assert_eq!(get["operationId"], "get_user");
+    assert_eq!(get["summary"], "Fetch a user");
+    assert_eq!(get["parameters"][0]["schema"]["type"], "integer");
+    assert_eq!(paths["/users/{id}"]["head"]["operationId"], "get_user_head");
+    assert_eq!(
+        paths["/legacy/users/{id}"]["get"]["operationId"],
+        "get_user_get"
+    );
+    assert!(
+        paths["/legacy/users/{id}"]["get"]["responses"]
+            .get("200")
+            .is_some()
+    );
+
+    // Explicit operationId is the base for disambiguation
+    let lines = vec![
+        "@route GET /a".to_string(),
+        "@route POST /a".to_string(),
+        "@operation-id touch".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "handler").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    assert_eq!(root["paths"]["/a"]["get"]["operationId"], "touch");
+    assert_eq!(root["paths"]["/a"]["post"]["operationId"], "touch_post");
+}
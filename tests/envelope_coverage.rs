@@ -0,0 +1,182 @@
+mod common;
+
+use common::{scan, scan_with, try_scan};
+use oas_forge::error::Error;
+use oas_forge::scanner::ScanOptions;
+use serde_json::json;
+
+const MODELS: &str = r#"
+    /// @openapi<T>
+    pub struct ApiResponse<T> { pub data: T, pub meta: String }
+
+    /// @openapi
+    pub struct ApiError { pub message: String }
+
+    /// @openapi
+    pub struct User { pub id: u32 }
+"#;
+
+#[test]
+fn test_module_response_envelope() {
+    let source = format!(
+        r#"
+        //! @response-envelope ApiResponse
+        //! @error-envelope ApiError
+        {MODELS}
+        /// Get user
+        /// @route GET /users/{{id: u32}}
+        /// @return 200: $User
+        /// @return 404: "Not found"
+        fn get_user() {{}}
+
+        /// Export
+        /// @route GET /export
+        /// @raw
+        /// @return 200: $User
+        /// @return 404: "Not found"
+        fn export() {{}}
+        "#
+    );
+    let root = scan(&source);
+
+    let responses = &root["paths"]["/users/{id}"]["get"]["responses"];
+    assert_eq!(
+        responses["200"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/ApiResponse_User" })
+    );
+    assert_eq!(
+        responses["404"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/ApiError" })
+    );
+    let wrapper = &root["components"]["schemas"]["ApiResponse_User"];
+    assert_eq!(
+        wrapper["properties"]["data"],
+        json!({ "$ref": "#/components/schemas/User" })
+    );
+
+    let raw = &root["paths"]["/export"]["get"]["responses"];
+    assert_eq!(
+        raw["200"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/User" })
+    );
+    assert!(raw["404"].get("content").is_none());
+}
+
+#[test]
+fn test_configured_envelope_and_override() {
+    let source = format!(
+        r#"
+        {MODELS}
+        /// Get user
+        /// @route GET /users/{{id: u32}}
+        /// @return 200: $User
+        fn get_user() {{}}
+
+        mod health {{
+            //! @response-envelope none
+
+            /// Health
+            /// @route GET /health
+            /// @return 200: $User
+            fn health() {{}}
+        }}
+        "#
+    );
+    let options = ScanOptions {
+        route_defaults: vec!["@response-envelope ApiResponse".to_string()],
+        ..Default::default()
+    };
+    let root = scan_with(&source, &options);
+
+    assert_eq!(
+        root["paths"]["/users/{id}"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+            ["$ref"],
+        "#/components/schemas/ApiResponse_User"
+    );
+    assert_eq!(
+        root["paths"]["/health"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+            ["$ref"],
+        "#/components/schemas/User"
+    );
+}
+
+#[test]
+fn test_envelope_wraps_inline_bodies() {
+    let source = format!(
+        r#"
+        //! @response-envelope ApiResponse
+        //! @error-envelope ApiError
+        {MODELS}
+        /// @openapi<T>
+        pub struct Page<T> {{ pub items: Vec<T> }}
+
+        /// List users
+        /// @route GET /users
+        /// @return 200: Vec<User>
+        /// @return 201: String
+        /// @return 304: "Not modified"
+        /// @return 4XX: "Client error"
+        fn list_users() {{}}
+
+        /// Paged users
+        /// @route GET /pages
+        /// @return 200: $Page<User>
+        fn pages() {{}}
+        "#
+    );
+    let root = scan(&source);
+
+    let responses = &root["paths"]["/users"]["get"]["responses"];
+    let list = &responses["200"]["content"]["application/json"]["schema"];
+    assert_eq!(
+        list["properties"]["data"],
+        json!({ "type": "array", "items": { "$ref": "#/components/schemas/User" } })
+    );
+    assert_eq!(list["properties"]["meta"], json!({ "type": "string" }));
+    assert_eq!(
+        responses["201"]["content"]["application/json"]["schema"]["properties"]["data"],
+        json!({ "type": "string" })
+    );
+    assert!(responses["304"].get("content").is_none());
+    assert_eq!(
+        responses["4XX"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ApiError"
+    );
+
+    let schemas = root["components"]["schemas"].as_object().unwrap();
+    assert!(
+        !schemas
+            .keys()
+            .any(|name| name.starts_with("ApiResponse_Vec"))
+    );
+    assert_eq!(
+        root["paths"]["/pages"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+            ["$ref"],
+        "#/components/schemas/ApiResponse_Page_User"
+    );
+    assert_eq!(
+        schemas["ApiResponse_Page_User"]["properties"]["data"]["$ref"],
+        "#/components/schemas/Page_User"
+    );
+}
+
+#[test]
+fn test_envelope_without_blueprint_fails_with_location() {
+    let source = format!(
+        r#"
+        //! @response-envelope ApiError
+        {MODELS}
+        /// List users
+        /// @route GET /users
+        /// @return 200: Vec<User>
+        fn list_users() {{}}
+        "#
+    );
+    match try_scan(&source, &ScanOptions::default()) {
+        Err(Error::InvalidRoute { file, line }) => {
+            assert!(file.ends_with("api.rs"));
+            assert_eq!(line, 13);
+        }
+        other => panic!("expected InvalidRoute, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,84 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_error_enum_responses() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+        /// @openapi
+        pub struct User { pub id: u32 }
+
+        /// @openapi
+        pub struct FieldError { pub field: String }
+
+        pub enum ApiError {
+            /// User not found
+            /// @openapi status 404
+            UserNotFound,
+            /// @openapi status 404 "Team not found"
+            TeamNotFound,
+            /// @openapi status 422
+            Validation(Vec<FieldError>),
+            /// @openapi status 409 $User "Already exists"
+            Conflict { id: u32 },
+            /// @openapi status 422 "Invalid range"
+            Range { min: u32, max: u32 },
+            /// Not mapped
+            Internal,
+        }
+
+        /// Get user
+        /// @route GET /users/{id: u32}
+        async fn get_user() -> Result<Json<User>, ApiError> {}
+
+        /// Create user
+        /// @route POST /users
+        /// @errors $ApiError
+        /// @return 409: "Duplicate"
+        fn create_user() {}
+
+        /// Ping
+        /// @route GET /ping
+        async fn ping() -> Result<Json<String>, StatusCode> {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+
+    let inferred = &root["paths"]["/users/{id}"]["get"]["responses"];
+    assert_eq!(
+        inferred["404"]["description"],
+        "User not found / Team not found"
+    );
+    assert!(inferred["404"].get("content").is_none());
+    assert_eq!(
+        inferred["422"]["content"]["application/json"]["schema"]["oneOf"][0],
+        json!({ "type": "array", "items": { "$ref": "#/components/schemas/FieldError" } })
+    );
+    assert_eq!(
+        inferred["422"]["content"]["application/json"]["schema"]["oneOf"][1]["required"],
+        json!(["min", "max"])
+    );
+    assert_eq!(
+        inferred["409"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/User"
+    );
+    assert_eq!(inferred["200"]["description"], "");
+    assert_eq!(inferred.as_object().unwrap().len(), 4);
+
+    let explicit = &root["paths"]["/users"]["post"]["responses"];
+    assert_eq!(explicit["409"]["description"], "Duplicate");
+    assert!(explicit.get("404").is_some());
+
+    let ping = &root["paths"]["/ping"]["get"]["responses"];
+    assert_eq!(ping.as_object().unwrap().len(), 1);
+    assert!(root["components"]["schemas"].get("ApiError").is_none());
+}
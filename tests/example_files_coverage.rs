@@ -0,0 +1,139 @@
+use oas_forge::error::Error;
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn test_example_files_loaded_for_schemas_and_routes() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir_all(src.join("fixtures")).unwrap();
+
+    std::fs::write(
+        src.join("fixtures/user.json"),
+        r#"{ "id": 7, "name": "Alice" }"#,
+    )
+    .unwrap();
+    std::fs::write(src.join("fixtures/create.yaml"), "name: Bob\n").unwrap();
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+        /// @openapi
+        /// example-file: fixtures/user.json
+        pub struct User { pub id: u32, pub name: String }
+
+        /// @route POST /users
+        /// @body $User
+        /// @example-body bob fixtures/create.yaml
+        /// @return 201: $User "Created"
+        /// @example-response 201 alice fixtures/user.json
+        fn create_user() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let merged = merge_openapi(snippets).unwrap();
+    let root: Value = serde_json::to_value(&merged).unwrap();
+
+    let schema = &root["components"]["schemas"]["User"];
+    assert_eq!(schema["examples"][0]["name"], "Alice");
+    assert!(schema.get("example-file").is_none());
+
+    let op = &root["paths"]["/users"]["post"];
+    assert_eq!(
+        op["requestBody"]["content"]["application/json"]["examples"]["bob"]["value"]["name"],
+        "Bob"
+    );
+    assert_eq!(
+        op["responses"]["201"]["content"]["application/json"]["examples"]["alice"]["value"]["id"],
+        7
+    );
+}
+
+#[test]
+fn test_missing_example_file_reports_location() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+/// @route GET /users
+/// @return 200: $User "OK"
+/// @example-response 200 missing.json
+fn list_users() {}
+"#,
+    )
+    .unwrap();
+
+    match scan_directories(&[src], &[]) {
+        Err(Error::ExampleFile {
+            file,
+            line,
+            example,
+            ..
+        }) => {
+            assert!(file.ends_with("api.rs"));
+            // Line of the directive, not of the item
+            assert_eq!(line, 4);
+            assert_eq!(example.to_str().unwrap(), "missing.json");
+        }
+        other => panic!("Expected ExampleFile error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_missing_field_example_file_reports_directive_line() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("model.rs"),
+        r#"
+/// @openapi
+struct User {
+    id: u32,
+    /// @openapi example-file: missing_name.json
+    name: String,
+}
+"#,
+    )
+    .unwrap();
+
+    match scan_directories(&[src], &[]) {
+        Err(Error::ExampleFile { line, example, .. }) => {
+            assert_eq!(line, 5);
+            assert_eq!(example.to_str().unwrap(), "missing_name.json");
+        }
+        other => panic!("Expected ExampleFile error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_missing_example_file_in_module_docs_reports_directive_line() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("health.rs"),
+        r#"//! @route GET /health
+//! @return 200: "OK"
+//!
+//! @example-response 200 up missing_health.json
+"#,
+    )
+    .unwrap();
+
+    match scan_directories(&[src], &[]) {
+        Err(Error::ExampleFile { line, example, .. }) => {
+            assert_eq!(line, 4);
+            assert_eq!(example.to_str().unwrap(), "missing_health.json");
+        }
+        other => panic!("Expected ExampleFile error, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,109 @@
+use oas_forge::Generator;
+use oas_forge::examples::synthesize_examples;
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_synthesize_from_keywords() {
+    let spec: serde_yaml_ng::Value = serde_yaml_ng::from_str(
+        r##"
+components:
+  schemas:
+    User:
+      type: object
+      properties:
+        id: { type: string, format: uuid }
+        email: { type: string, format: email }
+        age: { type: integer, minimum: 18, maximum: 99 }
+        score: { type: number, maximum: -1 }
+        role: { type: string, enum: [admin, member] }
+        active: { type: boolean, default: false }
+        code: { type: string, pattern: "^[0-9]+$" }
+        nick: { type: string, minLength: 8 }
+        tags: { type: array, minItems: 2, items: { type: string } }
+        parent: { $ref: "#/components/schemas/User" }
+        address: { $ref: "#/components/schemas/Address" }
+    Address:
+      type: object
+      properties:
+        city: { type: string }
+    Documented:
+      type: object
+      example: { keep: true }
+"##,
+    )
+    .unwrap();
+
+    let examples = synthesize_examples(&spec);
+    let user = &examples["User"];
+
+    assert_eq!(user["id"], "3fa85f64-5717-4562-b3fc-2c963f66afa6");
+    assert_eq!(user["email"], "user@example.com");
+    assert_eq!(user["age"], 18);
+    assert_eq!(user["score"], json!(-1.0));
+    assert_eq!(user["role"], "admin");
+    assert_eq!(user["active"], false);
+    assert_eq!(user["code"], "123");
+    assert_eq!(user["nick"], "stringxx");
+    assert_eq!(user["tags"], json!(["string", "string"]));
+    assert_eq!(user["address"], json!({ "city": "string" }));
+    // Cycle guard: self reference is dropped instead of recursing forever
+    assert!(user.get("parent").is_none());
+
+    assert!(!examples.contains_key("Documented"));
+}
+
+#[test]
+fn test_generator_inlines_and_exports_examples() {
+    let dir = tempdir().unwrap();
+    let src_dir = dir.path().join("src");
+    fs::create_dir(&src_dir).unwrap();
+
+    fs::write(
+        src_dir.join("main.rs"),
+        r#"
+        /// @openapi
+        /// openapi: 3.0.0
+        /// info: { title: "Examples", version: "1" }
+        fn main() {}
+
+        /// @openapi<T>
+        /// type: object
+        /// properties:
+        ///   items: { type: array, items: { $ref: $T } }
+        ///   total: { type: integer, minimum: 0 }
+        struct Page<T>(T);
+
+        /// @openapi
+        struct Item { name: String }
+
+        /// @route GET /items
+        /// @return 200: $Page<Item> "OK"
+        fn list_items() {}
+        "#,
+    )
+    .unwrap();
+
+    let out_full = dir.path().join("openapi.json");
+    let out_examples = dir.path().join("examples.json");
+
+    Generator::new()
+        .input(src_dir)
+        .output(&out_full)
+        .output_examples(&out_examples)
+        .synthesize_examples(true)
+        .generate()
+        .expect("generation failed");
+
+    let full: Value = serde_json::from_str(&fs::read_to_string(out_full).unwrap()).unwrap();
+    let schemas = &full["components"]["schemas"];
+    assert_eq!(schemas["Item"]["example"], json!({ "name": "string" }));
+    assert_eq!(
+        schemas["Page_Item"]["example"],
+        json!({ "items": [{ "name": "string" }], "total": 0 })
+    );
+
+    let exported: Value = serde_json::from_str(&fs::read_to_string(out_examples).unwrap()).unwrap();
+    assert_eq!(exported["Item"], json!({ "name": "string" }));
+}
@@ -0,0 +1,71 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_multipart_and_urlencoded_bodies() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+        /// @openapi
+        pub struct UploadForm {
+            /// Display name
+            pub name: String,
+            pub avatar: Vec<u8>,
+            pub thumbnail: Option<Bytes>,
+            /// @openapi file
+            pub document: DocumentPart,
+            pub tags: Vec<String>,
+        }
+
+        /// Upload
+        /// @route POST /uploads
+        /// @body $UploadForm multipart/form-data, application/x-www-form-urlencoded
+        /// @body $UploadForm application/json
+        /// @encoding avatar contentType=image/png,image/jpeg
+        /// @encoding avatar header X-Checksum: String "SHA-256 of the part"
+        /// @encoding tags style=form explode=false
+        fn upload() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let content = &root["paths"]["/uploads"]["post"]["requestBody"]["content"];
+
+    let multipart = &content["multipart/form-data"];
+    let props = &multipart["schema"]["properties"];
+    let binary = json!({ "type": "string", "format": "binary" });
+    assert_eq!(props["avatar"], binary);
+    assert_eq!(props["thumbnail"], binary);
+    assert_eq!(props["document"], binary);
+    assert_eq!(props["name"]["description"], "Display name");
+    assert_eq!(
+        multipart["schema"]["required"],
+        json!(["avatar", "document", "name", "tags"])
+    );
+
+    let avatar = &multipart["encoding"]["avatar"];
+    assert_eq!(avatar["contentType"], "image/png, image/jpeg");
+    assert_eq!(
+        avatar["headers"]["X-Checksum"]["description"],
+        "SHA-256 of the part"
+    );
+    assert_eq!(multipart["encoding"]["tags"]["explode"], false);
+
+    // urlencoded bodies are reflected too, byte fields stay arrays there
+    let form = &content["application/x-www-form-urlencoded"];
+    assert_eq!(form["schema"]["properties"]["avatar"]["type"], "array");
+    assert_eq!(form["encoding"]["tags"]["style"], "form");
+
+    // Alternative JSON body keeps the component reference, without encodings
+    assert_eq!(
+        content["application/json"],
+        json!({ "schema": { "$ref": "#/components/schemas/UploadForm" } })
+    );
+}
@@ -0,0 +1,121 @@
+use oas_forge::handlers::{AttributeRoute, route_attributes};
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn test_route_attribute_forms() {
+    let item: syn::ItemFn = syn::parse_str(
+        r#"
+        #[route("/users", method = "GET", method = "HEAD", wrap = "Logger")]
+        #[actix_web::post("/users/{id:\\d+}")]
+        #[oai(path = "/items/:id", method = "delete")]
+        #[get]
+        async fn handler() {}
+        "#,
+    )
+    .unwrap();
+
+    let routes = route_attributes(&item.attrs);
+    let route = |method: &str, path: &str| AttributeRoute {
+        method: method.to_string(),
+        path: path.to_string(),
+    };
+    assert_eq!(
+        routes,
+        vec![
+            route("get", "/users"),
+            route("head", "/users"),
+            route("post", r"/users/{id:\d+}"),
+            route("delete", "/items/:id"),
+        ]
+    );
+}
+
+#[test]
+fn test_actix_and_rocket_handlers_become_routes() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+        /// Fetch a user
+        #[get("/users/{id:\\d+}")]
+        async fn get_user(path: web::Path<u32>) -> impl Responder {}
+
+        /// Search posts
+        /// @query-param size: u8 "Page size"
+        #[get("/posts/<author>/<rest..>?<page>&<size>&<filter..>")]
+        fn search(author: &str, rest: PathBuf, page: Option<u32>, size: u8) {}
+
+        /// Create via rocket route
+        #[route(POST, uri = "/posts")]
+        fn create_post() {}
+
+        /// Explicit @route wins
+        /// @route PUT /explicit
+        #[put("/ignored")]
+        fn explicit() {}
+
+        #[delete("/undocumented")]
+        fn undocumented() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = &root["paths"];
+
+    let get_user = &paths["/users/{id}"]["get"];
+    assert_eq!(get_user["summary"], "Fetch a user");
+    assert_eq!(get_user["parameters"][0]["schema"]["type"], "integer");
+
+    let search = &paths["/posts/{author}/{rest}"]["get"];
+    let params = search["parameters"].as_array().unwrap();
+    let param = |name: &str| params.iter().find(|p| p["name"] == name).unwrap();
+    assert_eq!(param("author")["in"], "path");
+    assert_eq!(param("rest")["schema"]["type"], "string");
+    assert_eq!(param("page")["in"], "query");
+    assert_eq!(param("page")["required"], false);
+    assert_eq!(param("page")["schema"]["type"], "integer");
+    assert_eq!(param("size")["description"], "Page size");
+    assert_eq!(params.iter().filter(|p| p["name"] == "size").count(), 1);
+    assert!(params.iter().all(|p| p["name"] != "filter"));
+
+    assert!(paths["/posts"]["post"].is_object());
+    assert!(paths["/explicit"]["put"].is_object());
+    assert!(paths.get("/ignored").is_none());
+    assert!(paths.get("/undocumented").is_none());
+}
+
+#[test]
+fn test_repeated_attribute_methods_get_distinct_operation_ids() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+        /// List users
+        #[get("/users")]
+        #[get("/members")]
+        #[post("/users")]
+        #[get("/people")]
+        async fn list() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = &root["paths"];
+    assert_eq!(paths["/users"]["get"]["operationId"], "list");
+    assert_eq!(paths["/members"]["get"]["operationId"], "list_get_2");
+    assert_eq!(paths["/users"]["post"]["operationId"], "list_post");
+    assert_eq!(paths["/people"]["get"]["operationId"], "list_get_3");
+}
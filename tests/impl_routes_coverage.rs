@@ -0,0 +1,67 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_routes_on_impl_and_trait_methods() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("controllers.rs"),
+        r#"
+        /// @openapi
+        pub struct User { pub id: u32 }
+
+        /// @route-prefix /users
+        /// @tag Users
+        impl UserController {
+            /// List users
+            /// @route GET /
+            async fn list(&self) -> Json<Vec<User>> {}
+
+            /// Get user
+            /// @route GET /{id: u32}
+            /// @tag Lookup
+            async fn get(&self) -> Json<User> {}
+
+            fn helper(&self) {}
+        }
+
+        /// @tag Health
+        pub trait HealthService {
+            /// Liveness
+            /// @route GET /health
+            /// @operation-id liveness
+            fn live(&self);
+
+            /// Readiness
+            /// @route GET /ready
+            fn ready(&self) {}
+        }
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = &root["paths"];
+
+    let list = &paths["/users"]["get"];
+    assert_eq!(list["operationId"], "UserController_list");
+    assert_eq!(list["tags"], json!(["Users"]));
+    assert_eq!(
+        list["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+        "#/components/schemas/User"
+    );
+
+    let get = &paths["/users/{id}"]["get"];
+    assert_eq!(get["operationId"], "UserController_get");
+    assert_eq!(get["tags"], json!(["Lookup"]));
+
+    assert_eq!(paths["/health"]["get"]["operationId"], "liveness");
+    assert_eq!(paths["/health"]["get"]["tags"], json!(["Health"]));
+    assert_eq!(paths["/ready"]["get"]["operationId"], "HealthService_ready");
+    assert_eq!(paths.as_object().unwrap().len(), 4);
+}
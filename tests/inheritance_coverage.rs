@@ -0,0 +1,118 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use oas_forge::visitor::{ExtractedItem, OpenApiVisitor};
+use serde_json::Value;
+use syn::visit::Visit;
+use tempfile::tempdir;
+
+#[test]
+fn test_extends_generates_all_of() {
+    let code = r#"
+        /// Administrative user.
+        /// @openapi extends $User
+        pub struct AdminUser {
+            pub permissions: Vec<String>,
+        }
+    "#;
+
+    let mut visitor = OpenApiVisitor::default();
+    visitor.visit_file(&syn::parse_str::<syn::File>(code).unwrap());
+
+    let content = match &visitor.items[0] {
+        ExtractedItem::Schema { content, .. } => content,
+        _ => panic!("Expected schema"),
+    };
+    let root: Value = serde_yaml_ng::from_str(content).unwrap();
+    let schema = &root["components"]["schemas"]["AdminUser"];
+
+    assert_eq!(schema["description"], "Administrative user.");
+    assert_eq!(schema["allOf"][0]["$ref"], "$User");
+    assert_eq!(schema["allOf"][1]["type"], "object");
+    assert_eq!(
+        schema["allOf"][1]["properties"]["permissions"]["type"],
+        "array"
+    );
+    assert!(schema["allOf"][1].get("description").is_none());
+}
+
+#[test]
+fn test_extends_registers_subtype_in_discriminator() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("base.rs"),
+        r#"
+        /// @openapi
+        /// discriminator:
+        ///   propertyName: kind
+        pub struct User {
+            pub kind: String,
+            pub name: String,
+        }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("admin.rs"),
+        r#"
+        /// @openapi extends $User mapping=admin
+        pub struct AdminUser {
+            pub permissions: Vec<String>,
+        }
+
+        /// @openapi extends $User
+        pub struct GuestUser;
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let merged = merge_openapi(snippets).unwrap();
+    let yaml = serde_yaml_ng::to_string(&merged).unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let schemas = &root["components"]["schemas"];
+
+    assert_eq!(
+        schemas["AdminUser"]["allOf"][0]["$ref"],
+        "#/components/schemas/User"
+    );
+    assert!(!yaml.contains("x-oas-forge-extends"));
+
+    let one_of = schemas["User"]["oneOf"].as_array().unwrap();
+    assert_eq!(one_of.len(), 2);
+    let mapping = &schemas["User"]["discriminator"]["mapping"];
+    assert_eq!(mapping["admin"], "#/components/schemas/AdminUser");
+    assert_eq!(mapping["GuestUser"], "#/components/schemas/GuestUser");
+}
+
+#[test]
+fn test_extends_without_discriminator_leaves_base_untouched() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("models.rs"),
+        r#"
+        /// @openapi
+        pub struct Base { pub id: u32 }
+
+        /// @openapi extends $Base
+        pub struct Derived { pub extra: bool }
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let merged = merge_openapi(snippets).unwrap();
+    let root: Value = serde_json::to_value(&merged).unwrap();
+
+    assert!(root["components"]["schemas"]["Base"].get("oneOf").is_none());
+    assert!(
+        root["components"]["schemas"]["Derived"]
+            .get("x-oas-forge-extends")
+            .is_none()
+    );
+}
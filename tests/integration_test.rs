@@ -1,3 +1,5 @@
+#![allow(clippy::write_literal)]
+
 use oas_forge::scanner::scan_directories;
 use std::fs::File;
 use std::io::Write;
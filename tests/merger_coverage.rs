@@ -1,7 +1,10 @@
-use oas_forge::merger::merge_openapi;
+use oas_forge::Generator;
+use oas_forge::error::Error;
+use oas_forge::merger::{dangling_links, merge_openapi};
 use oas_forge::scanner::Snippet;
 
 use std::path::PathBuf;
+use tempfile::tempdir;
 
 #[test]
 fn test_deep_merge_objects() {
@@ -127,3 +130,89 @@ fn test_conflict_resolution_last_wins() {
 
     assert_eq!(merged["info"]["title"], "Title 2");
 }
+
+#[test]
+fn test_dangling_links() {
+    let snippet = Snippet {
+        content: r#"
+paths:
+  /users:
+    post:
+      operationId: create_user
+      responses:
+        '201':
+          description: Created
+          links:
+            GetUser: { operationId: get_user }
+            Self: { operationId: create_user }
+        '202':
+          description: Accepted
+          links:
+            ListPosts: { operationId: list_posts }
+"#
+        .to_string(),
+        file_path: PathBuf::from("f1.rs"),
+        line_number: 1,
+        operation_id: None,
+    };
+
+    let parsed: serde_yaml_ng::Value = serde_yaml_ng::from_str(&snippet.content).unwrap();
+    assert_eq!(
+        dangling_links(&parsed),
+        vec![
+            ("GetUser".to_string(), "get_user".to_string()),
+            ("ListPosts".to_string(), "list_posts".to_string()),
+        ]
+    );
+
+    // The merge itself is relaxed: fragments may link to operations of other crates
+    assert!(merge_openapi(vec![snippet]).is_ok());
+}
+
+#[test]
+fn test_dangling_links_fail_only_the_full_spec() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        /// @openapi
+        /// openapi: 3.0.0
+        /// info: { title: "Links", version: "1" }
+        fn main() {}
+
+        /// Create user
+        /// @route POST /users
+        /// @return 201: "Created"
+        /// @return 202: "Accepted"
+        /// @link 201 GetUser operationId=get_user
+        /// @link 202 ListPosts operationId=list_posts
+        fn create_user() {}
+        "#,
+    )
+    .unwrap();
+
+    let result = Generator::new()
+        .input(&src)
+        .output(dir.path().join("openapi.json"))
+        .generate();
+    match result {
+        Err(Error::DanglingLink { links }) => {
+            assert_eq!(
+                links,
+                vec!["'GetUser' -> get_user", "'ListPosts' -> list_posts"]
+            );
+        }
+        other => panic!("Expected DanglingLink, got {:?}", other),
+    }
+    assert!(!dir.path().join("openapi.json").exists());
+
+    let paths = dir.path().join("paths.json");
+    Generator::new()
+        .input(&src)
+        .output_paths(&paths)
+        .generate()
+        .unwrap();
+    assert!(paths.exists());
+}
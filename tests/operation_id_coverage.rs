@@ -0,0 +1,136 @@
+use oas_forge::dsl::OperationIdStrategy;
+use oas_forge::error::Error;
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::{ScanOptions, scan_directories_with};
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn write_tree(src: &std::path::Path) {
+    std::fs::create_dir_all(src.join("users")).unwrap();
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        mod users;
+
+        mod admin {
+            /// List admins
+            /// @route GET /admins
+            fn list() {}
+        }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("users").join("mod.rs"),
+        r#"
+        /// List users
+        /// @route GET /users/{id: u32}/friends
+        fn list() {}
+
+        /// Pinned
+        /// @route GET /pinned
+        /// @operation-id pinned
+        fn pinned() {}
+        "#,
+    )
+    .unwrap();
+}
+
+fn operation_ids(options: &ScanOptions) -> Vec<String> {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    write_tree(&src);
+    let (snippets, _) = scan_directories_with(&[src], &[], options).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let mut ids: Vec<String> = root["paths"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|item| item["get"]["operationId"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_operation_id_strategies() {
+    let options = |strategy| ScanOptions {
+        operation_ids: strategy,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        operation_ids(&options(OperationIdStrategy::ModulePath)),
+        vec!["admin_list", "pinned", "users_list"]
+    );
+    assert_eq!(
+        operation_ids(&options(OperationIdStrategy::MethodPath)),
+        vec!["get_admins", "get_users_id_friends", "pinned"]
+    );
+    assert_eq!(
+        operation_ids(&options(
+            OperationIdStrategy::parse("{method}-{module}.{fn}").unwrap()
+        )),
+        vec!["get-admin.list", "get-users.list", "pinned"]
+    );
+    assert_eq!(OperationIdStrategy::parse("unknown"), None);
+}
+
+#[test]
+fn test_duplicate_operation_ids_report_both_locations() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    write_tree(&src);
+    let (snippets, _) = scan_directories_with(&[src], &[], &ScanOptions::default()).unwrap();
+
+    match merge_openapi(snippets) {
+        Err(Error::DuplicateOperationId {
+            operation_id,
+            first_file,
+            second_file,
+            ..
+        }) => {
+            assert_eq!(operation_id, "list");
+            let mut files = [first_file, second_file]
+                .map(|f| f.file_name().unwrap().to_string_lossy().into_owned());
+            files.sort();
+            assert_eq!(files, ["main.rs".to_string(), "mod.rs".to_string()]);
+        }
+        other => panic!("Expected DuplicateOperationId, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_virtual_routes_in_separate_files() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("a.rs"),
+        "//! @route GET /a\n//! @return 200: \"A\"\n",
+    )
+    .unwrap();
+    std::fs::write(src.join("b.rs"), "//! @route GET /b/{id: u32}\n").unwrap();
+    std::fs::write(
+        src.join("c.rs"),
+        "//! @route GET /c\n//! @operation-id customC\n",
+    )
+    .unwrap();
+
+    let roots = [src];
+    let ids = |options: &ScanOptions| {
+        let (snippets, _) = scan_directories_with(&roots, &[], options).unwrap();
+        let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+        ["/a", "/b/{id}", "/c"].map(|path| root["paths"][path]["get"]["operationId"].clone())
+    };
+
+    assert_eq!(
+        ids(&ScanOptions::default()),
+        ["get_a", "get_b_id", "customC"]
+    );
+    let options = ScanOptions {
+        operation_ids: OperationIdStrategy::ModulePath,
+        ..Default::default()
+    };
+    assert_eq!(ids(&options), ["a_get_a", "b_get_b_id", "customC"]);
+}
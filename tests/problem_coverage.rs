@@ -0,0 +1,60 @@
+mod common;
+
+use common::scan;
+use serde_json::json;
+
+#[test]
+fn test_problem_responses() {
+    let root = scan(
+        r#"
+        //! @default-problem 500 "Server error"
+
+        /// @openapi
+        pub struct ValidationProblem { pub errors: Vec<String> }
+
+        /// Get user
+        /// @route GET /users/{id: u32}
+        /// @problem 404 "Not found"
+        /// @problem 422 $ValidationProblem "Invalid input"
+        fn get_user() {}
+        "#,
+    );
+    let responses = &root["paths"]["/users/{id}"]["get"]["responses"];
+
+    let not_found = &responses["404"];
+    assert_eq!(not_found["description"], "Not found");
+    assert_eq!(
+        not_found["content"]["application/problem+json"]["schema"],
+        json!({ "$ref": "#/components/schemas/Problem" })
+    );
+    assert_eq!(
+        responses["422"]["content"]["application/problem+json"]["schema"]["allOf"],
+        json!([
+            { "$ref": "#/components/schemas/Problem" },
+            { "$ref": "#/components/schemas/ValidationProblem" }
+        ])
+    );
+    assert_eq!(responses["500"]["description"], "Server error");
+
+    let problem = &root["components"]["schemas"]["Problem"];
+    assert_eq!(problem["properties"]["status"]["type"], "integer");
+    assert_eq!(problem["properties"]["type"]["default"], "about:blank");
+}
+
+#[test]
+fn test_project_problem_schema_wins() {
+    let root = scan(
+        r#"
+        /// @openapi
+        pub struct Problem { pub code: String }
+
+        /// @route GET /items
+        /// @problem 404
+        fn list_items() {}
+        "#,
+    );
+    let problem = &root["components"]["schemas"]["Problem"];
+    assert!(problem["properties"].get("code").is_some());
+    assert!(problem["properties"].get("detail").is_none());
+    assert!(root.get("paths").is_some());
+}
@@ -0,0 +1,136 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::{ScanOptions, scan_directories_with};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_scoped_defaults_and_overrides() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        //! @default-security bearerAuth
+        //! @default-response 401 $Problem "Unauthorized"
+
+        /// @openapi
+        pub struct Problem { pub title: String }
+
+        /// @default-tag Users
+        mod users;
+
+        /// @default-tag Admin
+        /// @default-security adminAuth("write")
+        mod admin {
+            /// Purge
+            /// @route DELETE /admin/cache
+            fn purge() {}
+        }
+
+        /// Health
+        /// @route GET /health
+        /// @security none
+        /// @tag Ops
+        fn health() {}
+
+        /// Raw
+        /// @route GET /raw
+        /// @defaults none
+        fn raw() {}
+        "#,
+    )
+    .unwrap();
+
+    std::fs::write(
+        src.join("users.rs"),
+        r#"
+        /// List users
+        /// @route GET /users
+        /// @return 401: "Custom unauthorized"
+        /// @header-param x-request-id: String "Own header"
+        fn list_users() {}
+        "#,
+    )
+    .unwrap();
+
+    let options = ScanOptions {
+        route_defaults: vec![
+            "@default-header-param X-Request-Id: Uuid \"Correlation id\"".to_string(),
+            "@default-tag Api".to_string(),
+        ],
+        ..Default::default()
+    };
+    let (snippets, _) = scan_directories_with(&[src], &[], &options).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = &root["paths"];
+
+    let purge = &paths["/admin/cache"]["delete"];
+    assert_eq!(purge["tags"], json!(["Admin"]));
+    assert_eq!(purge["security"], json!([{ "adminAuth": ["write"] }]));
+    assert_eq!(
+        purge["responses"]["401"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/Problem"
+    );
+    assert_eq!(purge["parameters"][0]["name"], "X-Request-Id");
+    assert_eq!(purge["parameters"][0]["schema"]["format"], "uuid");
+
+    let list = &paths["/users"]["get"];
+    assert_eq!(list["tags"], json!(["Users"]));
+    assert_eq!(list["security"], json!([{ "bearerAuth": [] }]));
+    assert_eq!(
+        list["responses"]["401"]["description"],
+        "Custom unauthorized"
+    );
+    assert_eq!(list["parameters"].as_array().unwrap().len(), 1);
+    assert_eq!(list["parameters"][0]["description"], "Own header");
+
+    let health = &paths["/health"]["get"];
+    assert_eq!(health["security"], json!([]));
+    assert_eq!(health["tags"], json!(["Ops"]));
+    assert_eq!(health["responses"]["401"]["description"], "Unauthorized");
+
+    let raw = &paths["/raw"]["get"];
+    assert!(raw.get("security").is_none());
+    assert!(raw["responses"].get("401").is_none());
+    assert_eq!(raw["parameters"], json!([]));
+}
+
+#[test]
+fn test_default_security_none_overrides_root_security() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        //! @openapi
+        //! openapi: 3.1.0
+        //! info:
+        //!   title: Api
+        //!   version: 1.0.0
+        //! security:
+        //!   - bearerAuth: []
+
+        /// @default-security none
+        mod public {
+            /// Health
+            /// @route GET /health
+            fn health() {}
+        }
+
+        /// Me
+        /// @route GET /me
+        fn me() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories_with(&[src], &[], &ScanOptions::default()).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+
+    assert_eq!(root["paths"]["/health"]["get"]["security"], json!([]));
+    assert!(root["paths"]["/me"]["get"].get("security").is_none());
+}
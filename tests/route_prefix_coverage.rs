@@ -0,0 +1,95 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::{ScanOptions, scan_directories_with};
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn test_route_prefixes_stack_across_modules() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir_all(src.join("users")).unwrap();
+
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        //! @route-prefix /api/v1
+        //!
+        //! @route GET /ping
+        //! @return 200: "pong"
+
+        /// @route-prefix /users
+        mod users;
+
+        /// @route-prefix /admin
+        mod admin {
+            /// Stats
+            /// @route GET /stats
+            fn stats() {}
+        }
+
+        /// Health
+        /// @route GET ^/health
+        fn health() {}
+
+        /// Root
+        /// @route GET /
+        fn index() {}
+        "#,
+    )
+    .unwrap();
+
+    std::fs::write(
+        src.join("users").join("mod.rs"),
+        r#"
+        mod avatars;
+
+        /// Get user
+        /// @route GET /{id: u32}
+        fn get_user() {}
+
+        /// List users
+        async fn list_users() -> Json<Vec<User>> {}
+
+        fn router() -> Router {
+            Router::new().route("/", get(list_users))
+        }
+        "#,
+    )
+    .unwrap();
+
+    std::fs::write(
+        src.join("users").join("avatars.rs"),
+        r#"
+        //! @route-prefix /avatars
+
+        /// Avatar
+        /// @route GET /{id: u32}
+        fn avatar() {}
+        "#,
+    )
+    .unwrap();
+
+    let options = ScanOptions {
+        infer_routes: true,
+        ..Default::default()
+    };
+    let (snippets, _) = scan_directories_with(&[src], &[], &options).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = root["paths"].as_object().unwrap();
+
+    let mut keys: Vec<&str> = paths.keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec![
+            "/api/v1",
+            "/api/v1/admin/stats",
+            "/api/v1/ping",
+            "/api/v1/users",
+            "/api/v1/users/avatars/{id}",
+            "/api/v1/users/{id}",
+            "/health",
+        ]
+    );
+    assert_eq!(paths["/api/v1/users"]["get"]["operationId"], "list_users");
+}
@@ -0,0 +1,173 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::{ScanOptions, scan_directories, scan_directories_with};
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn write_app(src: &std::path::Path) {
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        use axum::{Router, routing::{get, post}};
+
+        fn app() -> Router {
+            let users = Router::new()
+                .route("/", get(list_users).post(create_user))
+                .route("/:id", get(get_user).delete(delete_user));
+
+            Router::new()
+                .nest("/api/v1", Router::new().nest("/users", users).merge(files()))
+                .route("/health", get(health))
+        }
+
+        async fn main() {
+            let app = app().layer(TraceLayer::new_for_http());
+            axum::serve(listener, app).await.unwrap();
+        }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("handlers.rs"),
+        r#"
+        use axum::{Router, routing::{get, on, MethodFilter}};
+
+        pub fn files() -> Router {
+            Router::new().route("/files/{*path}", on(MethodFilter::GET, download))
+        }
+
+        /// List users
+        async fn list_users() {}
+
+        /// Create a user
+        /// @return 201: "Created"
+        async fn create_user() {}
+
+        /// Fetch a user
+        /// @path-param id: u32 "User ID"
+        async fn get_user() {}
+
+        /// Explicit routes always win.
+        /// @route DELETE /users/{id: u32}
+        async fn delete_user() {}
+
+        /// Download a file
+        async fn download() {}
+
+        async fn health() {}
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_routes_inferred_from_router_chains() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    write_app(&src);
+
+    let options = ScanOptions {
+        infer_routes: true,
+        ..Default::default()
+    };
+    let (snippets, _) = scan_directories_with(&[src], &[], &options).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = &root["paths"];
+
+    assert_eq!(paths["/api/v1/users"]["get"]["summary"], "List users");
+    assert_eq!(paths["/api/v1/users"]["get"]["operationId"], "list_users");
+    assert_eq!(
+        paths["/api/v1/users"]["post"]["responses"]["201"]["description"],
+        "Created"
+    );
+
+    let get_user = &paths["/api/v1/users/{id}"]["get"];
+    assert_eq!(get_user["parameters"][0]["name"], "id");
+    assert_eq!(get_user["parameters"][0]["schema"]["type"], "integer");
+
+    // Wildcard normalized, parameter defaults to string
+    let download = &paths["/api/v1/files/{path}"]["get"];
+    assert_eq!(download["parameters"][0]["schema"]["type"], "string");
+
+    // Explicit @route is kept as written, undocumented handlers are skipped
+    assert!(paths["/users/{id}"]["delete"].is_object());
+    assert!(paths["/api/v1/users/{id}"].get("delete").is_none());
+    assert!(paths.get("/health").is_none());
+}
+
+#[test]
+fn test_route_inference_is_opt_in() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    write_app(&src);
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    assert!(root["paths"].get("/api/v1/users").is_none());
+    assert!(root["paths"]["/users/{id}"]["delete"].is_object());
+}
+
+#[test]
+fn test_same_named_handlers_resolve_by_module() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        mod users;
+        mod admin;
+
+        fn app() -> Router {
+            Router::new()
+                .nest("/users", users::routes())
+                .nest("/admin", crate::admin::routes())
+                .route("/all", get(list))
+        }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("users.rs"),
+        r#"
+        /// List users
+        async fn list() {}
+
+        pub fn routes() -> Router {
+            Router::new().route("/", get(list))
+        }
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("admin.rs"),
+        r#"
+        /// List admins
+        async fn list() {}
+
+        pub fn routes() -> Router {
+            Router::new().route("/", get(self::list))
+        }
+        "#,
+    )
+    .unwrap();
+
+    let options = ScanOptions {
+        infer_routes: true,
+        ..Default::default()
+    };
+    let (snippets, _) = scan_directories_with(&[src], &[], &options).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let paths = &root["paths"];
+
+    assert_eq!(paths["/users"]["get"]["summary"], "List users");
+    assert_eq!(paths["/admin"]["get"]["summary"], "List admins");
+    assert_ne!(
+        paths["/users"]["get"]["operationId"],
+        paths["/admin"]["get"]["operationId"]
+    );
+    // A bare `list` matches both modules and is skipped
+    assert!(paths.get("/all").is_none());
+}
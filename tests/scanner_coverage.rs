@@ -1,4 +1,5 @@
 use oas_forge::index::Registry;
+use oas_forge::preprocessor;
 use oas_forge::scanner::{Snippet, preprocess_macros};
 use std::path::PathBuf;
 
@@ -62,3 +63,12 @@ fn test_macro_extend_auto_quote() {
     // Expect x-openapi-extend
     assert!(processed.content.contains("x-openapi-extend: '''User'''"));
 }
+
+#[test]
+fn test_plain_text_is_not_folded_into_a_scalar() {
+    let registry = Registry::new();
+    // Doc lines of a route block starting with a summary line parse as a YAML scalar
+    let input = " Get users\n @route GET /users";
+    let processed = preprocessor::preprocess(input, &registry);
+    assert_eq!(processed, input);
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{scan, try_scan};
+use oas_forge::error::Error;
+use oas_forge::scanner::ScanOptions;
+use serde_json::{Value, json};
+
+fn param<'a>(op: &'a Value, name: &str) -> &'a Value {
+    op["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == name)
+        .unwrap_or_else(|| panic!("missing parameter {}", name))
+}
+
+#[test]
+fn test_extractors_become_params_body_and_response() {
+    let root = scan(
+        r#"
+        /// @openapi
+        pub struct Filter {
+            /// Free text search
+            pub q: String,
+            pub page: Option<u32>,
+        }
+
+        /// @openapi
+        pub struct NewPost { pub title: String }
+
+        /// @openapi
+        pub struct Post { pub id: u32 }
+
+        /// Create a post
+        /// @route POST /orgs/{org}/posts/{slot}
+        async fn create_post(
+            State(db): State<Db>,
+            Path((org, slot)): Path<(Uuid, u32)>,
+            Query(filter): Query<Filter>,
+            TypedHeader(agent): TypedHeader<UserAgent>,
+            Json(body): Json<NewPost>,
+        ) -> Result<(StatusCode, Json<Post>), ApiError> {}
+        "#,
+    );
+
+    let op = &root["paths"]["/orgs/{org}/posts/{slot}"]["post"];
+    assert_eq!(param(op, "org")["schema"]["format"], "uuid");
+    assert_eq!(param(op, "slot")["schema"]["type"], "integer");
+    assert_eq!(param(op, "q")["in"], "query");
+    assert_eq!(param(op, "q")["required"], true);
+    assert_eq!(param(op, "q")["description"], "Free text search");
+    assert_eq!(param(op, "page")["required"], false);
+    assert_eq!(param(op, "User-Agent")["in"], "header");
+    assert!(
+        op["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|p| p["name"] != "db")
+    );
+
+    assert_eq!(
+        op["requestBody"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/NewPost" })
+    );
+    assert_eq!(
+        op["responses"]["200"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/Post" })
+    );
+}
+
+#[test]
+fn test_explicit_dsl_takes_precedence() {
+    let root = scan(
+        r#"
+        /// @openapi
+        pub struct UserPath { pub id: u32 }
+
+        /// @openapi
+        pub struct Form { pub name: String }
+
+        /// Update
+        /// @route PUT /users/{id}
+        /// @path-param id: String "Opaque id"
+        /// @body Form application/x-www-form-urlencoded
+        /// @return 204: "Updated"
+        async fn update(Path(p): Path<UserPath>, Json(f): Json<Form>) -> Json<Form> {}
+        "#,
+    );
+
+    let update = &root["paths"]["/users/{id}"]["put"];
+    let params = update["parameters"].as_array().unwrap();
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0]["schema"]["type"], "string");
+    assert!(
+        update["requestBody"]["content"]
+            .get("application/json")
+            .is_none()
+    );
+    assert!(update["responses"].get("200").is_none());
+}
+
+#[test]
+fn test_invalid_route_fails_with_location() {
+    let source = r#"
+/// @openapi
+pub struct Form { pub name: String }
+
+/// Fetch
+/// @route GET /files/{name}
+async fn fetch(Path(p): Path<FileParams>, Json(f): Json<Form>) {}
+"#;
+
+    // Unknown struct and no explicit declaration: `{name}` stays undeclared
+    match try_scan(source, &ScanOptions::default()) {
+        Err(Error::InvalidRoute { file, line }) => {
+            assert!(file.ends_with("api.rs"));
+            assert_eq!(line, 5);
+        }
+        other => panic!("Expected InvalidRoute error, got {:?}", other.map(|_| ())),
+    }
+}
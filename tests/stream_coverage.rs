@@ -0,0 +1,119 @@
+use oas_forge::dsl::parse_route_dsl;
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_stream_responses() {
+    let lines = vec![
+        "@route GET /events".to_string(),
+        "@stream 200: $Event \"Live updates\" sse".to_string(),
+        "@stream 200: $Event ndjson".to_string(),
+        "@stream 201: $Bad \"x\" xml".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "events").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let response = &root["paths"]["/events"]["get"]["responses"]["200"];
+    assert_eq!(response["description"], "Live updates");
+
+    let sse = &response["content"]["text/event-stream"];
+    assert_eq!(sse["schema"]["type"], "string");
+    let data = &sse["x-item-schema"]["properties"]["data"];
+    assert_eq!(data["contentMediaType"], "application/json");
+    assert_eq!(
+        data["contentSchema"],
+        json!({ "$ref": "#/components/schemas/Event" })
+    );
+
+    assert_eq!(
+        response["content"]["application/x-ndjson"]["x-item-schema"],
+        json!({ "$ref": "#/components/schemas/Event" })
+    );
+    assert!(
+        root["paths"]["/events"]["get"]["responses"]
+            .get("201")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_named_sse_events_and_item_schema_for_3_2() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("main.rs"),
+        r#"
+        //! @openapi
+        //! openapi: 3.2.0
+        //! info:
+        //!   title: Streams
+        //!   version: 1.0.0
+
+        /// @openapi
+        pub struct UserCreated { pub id: u32 }
+
+        /// Feed
+        /// @route GET /feed
+        /// @stream 200: "User feed" sse
+        /// @stream-event 200 created: $UserCreated "A user was created"
+        /// @stream-event 200 heartbeat:
+        fn feed() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let media = &root["paths"]["/feed"]["get"]["responses"]["200"]["content"]["text/event-stream"];
+    assert!(media.get("x-item-schema").is_none());
+    assert_eq!(media["schema"]["type"], "string");
+
+    let events = media["itemSchema"]["oneOf"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["properties"]["event"]["enum"], json!(["created"]));
+    assert_eq!(events[0]["description"], "A user was created");
+    assert_eq!(
+        events[0]["properties"]["data"]["contentSchema"]["$ref"],
+        "#/components/schemas/UserCreated"
+    );
+    assert_eq!(events[0]["required"], json!(["event", "data"]));
+    assert_eq!(
+        events[1]["properties"]["event"]["enum"],
+        json!(["heartbeat"])
+    );
+    assert!(
+        events[1]["properties"]["data"]
+            .get("contentSchema")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_plain_stream_item_joins_named_events() {
+    let lines = vec![
+        "@route GET /feed".to_string(),
+        "@stream 200: $Tick \"Feed\" sse".to_string(),
+        "@stream-event 200 created: $UserCreated".to_string(),
+        "@streamfoo 201: $Tick sse".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "feed").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let responses = &root["paths"]["/feed"]["get"]["responses"];
+
+    let events = responses["200"]["content"]["text/event-stream"]["x-item-schema"]["oneOf"]
+        .as_array()
+        .unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0]["properties"]["data"]["contentSchema"]["$ref"],
+        "#/components/schemas/Tick"
+    );
+    assert!(events[0]["properties"]["event"].get("enum").is_none());
+    assert_eq!(events[1]["properties"]["event"]["enum"], json!(["created"]));
+    assert_eq!(responses["200"]["description"], "Feed");
+
+    // `@streamfoo` is not `@stream`
+    assert!(responses.get("201").is_none());
+}
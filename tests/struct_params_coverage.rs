@@ -0,0 +1,75 @@
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::scan_directories;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_struct_expands_into_params() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("api.rs"),
+        r#"
+        /// @openapi
+        pub struct Pagination {
+            /// Page number
+            pub page: Option<u32>,
+            pub size: u32,
+        }
+
+        /// @openapi
+        pub struct Range { pub from: u32, pub to: u32 }
+
+        /// @openapi
+        pub struct Filter {
+            /// @openapi deprecated: true
+            pub legacy: Option<String>,
+            pub range: Range,
+        }
+
+        /// @openapi
+        pub struct Tracing {
+            /// Correlation id
+            pub x_request_id: String,
+        }
+
+        /// List items
+        /// @route GET /items
+        /// @query-params $Pagination
+        /// @query-params $Filter
+        /// @query-param size: u8 "Explicit wins"
+        /// @header-params $Tracing
+        /// @cookie-params $Missing
+        fn list_items() {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let params = root["paths"]["/items"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let param = |name: &str| params.iter().find(|p| p["name"] == name).unwrap().clone();
+
+    assert_eq!(param("page")["in"], "query");
+    assert_eq!(param("page")["required"], false);
+    assert_eq!(param("page")["description"], "Page number");
+    assert_eq!(param("page")["schema"]["type"], "integer");
+    assert_eq!(param("size")["description"], "Explicit wins");
+    assert_eq!(params.iter().filter(|p| p["name"] == "size").count(), 1);
+
+    assert_eq!(param("legacy")["deprecated"], true);
+    assert_eq!(param("range")["style"], "deepObject");
+    assert_eq!(param("range")["explode"], true);
+    assert_eq!(
+        param("range")["schema"],
+        json!({ "$ref": "#/components/schemas/Range" })
+    );
+
+    assert_eq!(param("x_request_id")["in"], "header");
+    assert_eq!(param("x_request_id")["required"], true);
+    assert!(params.iter().all(|p| p["in"] != "cookie"));
+}
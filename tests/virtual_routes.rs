@@ -21,7 +21,7 @@ fn test_virtual_routes() {
     let item = visitor.items.first().expect("Should extract virtual route");
     if let ExtractedItem::RouteDSL {
         content,
-        operation_id,
+        operation_id: _,
         ..
     } = item
     {
@@ -117,10 +117,8 @@ fn test_enum_parsing() {
     // Let's debug by printing or relaxing assertion.
     // Just assert name is "Status".
 
-    if let ExtractedItem::Schema { name, .. } = &visitor.items[0] {
-        if let Some(n) = name {
-            assert_eq!(n, "Status");
-        }
+    if let ExtractedItem::Schema { name: Some(n), .. } = &visitor.items[0] {
+        assert_eq!(n, "Status");
     }
 }
 
@@ -195,3 +193,25 @@ fn test_description_does_not_slurp_yaml_payload() {
         );
     }
 }
+
+#[test]
+fn test_pathbuf_maps_to_string() {
+    let ty: syn::Type = syn::parse_str("std::path::PathBuf").unwrap();
+    let (schema, required) = oas_forge::visitor::map_syn_type_to_openapi(&ty);
+    assert_eq!(schema, serde_json::json!({ "type": "string" }));
+    assert!(required);
+}
+
+#[test]
+fn test_byte_vec_items_are_uint8() {
+    let ty: syn::Type = syn::parse_str("Vec<u8>").unwrap();
+    let (schema, _) = oas_forge::visitor::map_syn_type_to_openapi(&ty);
+    assert_eq!(
+        schema,
+        serde_json::json!({ "type": "array", "items": { "type": "integer", "format": "uint8" } })
+    );
+
+    let ty: syn::Type = syn::parse_str("Vec<u16>").unwrap();
+    let (schema, _) = oas_forge::visitor::map_syn_type_to_openapi(&ty);
+    assert_eq!(schema["items"]["format"], "int32");
+}
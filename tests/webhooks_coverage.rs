@@ -0,0 +1,121 @@
+use oas_forge::dsl::{OperationIdStrategy, parse_route_dsl};
+use oas_forge::merger::merge_openapi;
+use oas_forge::scanner::{ScanOptions, scan_directories, scan_directories_with};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+#[test]
+fn test_webhooks_from_functions_and_modules() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("events.rs"),
+        r#"
+        /// @openapi
+        pub struct OrderEvent { pub id: u32 }
+
+        /// Order created
+        /// @webhook OrderCreated POST
+        /// @body $OrderEvent
+        /// @return 204: "Acknowledged"
+        fn order_created() {}
+
+        /// @webhook OrderShipped POST
+        /// Order shipped
+        /// @body $OrderEvent
+        /// @header-param X-Signature: String "HMAC signature"
+        ///
+        /// @webhook OrderCancelled PUT
+        /// Order cancelled
+        mod delivery {}
+        "#,
+    )
+    .unwrap();
+
+    let (snippets, _) = scan_directories(&[src], &[]).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+    let webhooks = &root["webhooks"];
+
+    let created = &webhooks["OrderCreated"]["post"];
+    assert_eq!(created["summary"], "Order created");
+    assert_eq!(created["operationId"], "order_created");
+    assert_eq!(
+        created["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/OrderEvent"
+    );
+    assert_eq!(created["responses"]["204"]["description"], "Acknowledged");
+
+    let shipped = &webhooks["OrderShipped"]["post"];
+    assert_eq!(shipped["summary"], "Order shipped");
+    assert_eq!(shipped["parameters"][0]["name"], "X-Signature");
+    assert_eq!(
+        webhooks["OrderCancelled"]["put"]["summary"],
+        "Order cancelled"
+    );
+    assert!(webhooks["OrderShipped"].get("put").is_none());
+    assert!(root.get("paths").is_none());
+}
+
+#[test]
+fn test_callbacks_on_routes() {
+    let lines = vec![
+        "@route POST /subscriptions".to_string(),
+        "@callback onEvent {$request.body#/callbackUrl} POST $Payload \"Event delivery\""
+            .to_string(),
+        "@callback onEvent {$request.body#/fallbackUrl} PUT".to_string(),
+        "@return 201: \"Subscribed\"".to_string(),
+    ];
+    let yaml = parse_route_dsl(&lines, "subscribe").unwrap();
+    let root: Value = serde_yaml_ng::from_str(&yaml).unwrap();
+    let callbacks = &root["paths"]["/subscriptions"]["post"]["callbacks"]["onEvent"];
+
+    let delivery = &callbacks["{$request.body#/callbackUrl}"]["post"];
+    assert_eq!(delivery["summary"], "Event delivery");
+    assert_eq!(
+        delivery["requestBody"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/Payload" })
+    );
+    assert_eq!(delivery["responses"]["200"]["description"], "OK");
+
+    let fallback = &callbacks["{$request.body#/fallbackUrl}"]["put"];
+    assert!(fallback.get("requestBody").is_none());
+}
+
+#[test]
+fn test_webhooks_inherit_module_scope() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("events.rs"),
+        r#"
+        mod billing {
+            //! @default-tag Billing
+
+            /// Invoice paid
+            /// @webhook InvoicePaid POST
+            fn invoice_paid() {}
+
+            /// List invoices
+            /// @route GET /invoices
+            fn list_invoices() {}
+        }
+        "#,
+    )
+    .unwrap();
+
+    let options = ScanOptions {
+        operation_ids: OperationIdStrategy::ModulePath,
+        ..Default::default()
+    };
+    let (snippets, _) = scan_directories_with(&[src], &[], &options).unwrap();
+    let root: Value = serde_json::to_value(merge_openapi(snippets).unwrap()).unwrap();
+
+    let paid = &root["webhooks"]["InvoicePaid"]["post"];
+    let list = &root["paths"]["/invoices"]["get"];
+    assert_eq!(paid["operationId"], "events_billing_invoice_paid");
+    assert_eq!(list["operationId"], "events_billing_list_invoices");
+    assert_eq!(paid["tags"], json!(["Billing"]));
+    assert_eq!(paid["tags"], list["tags"]);
+}
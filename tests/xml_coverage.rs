@@ -0,0 +1,72 @@
+use oas_forge::visitor::{ExtractedItem, OpenApiVisitor};
+use serde_json::{Value, json};
+use syn::visit::Visit;
+
+fn extract_schema(code: &str, name: &str) -> Value {
+    let mut visitor = OpenApiVisitor::default();
+    visitor.visit_file(&syn::parse_str::<syn::File>(code).unwrap());
+
+    let content = match &visitor.items[0] {
+        ExtractedItem::Schema { content, .. } => content,
+        _ => panic!("Expected schema"),
+    };
+    let root: Value = serde_yaml_ng::from_str(content).unwrap();
+    root["components"]["schemas"][name].clone()
+}
+
+#[test]
+fn test_serde_xml_conventions() {
+    let schema = extract_schema(
+        r#"
+        /// @openapi
+        #[serde(rename_all = "camelCase")]
+        pub struct Price {
+            #[serde(rename = "@currency")]
+            pub currency: String,
+            #[serde(rename = "$value")]
+            pub amount_value: f64,
+            pub tax_rate: f64,
+        }
+        "#,
+        "Price",
+    );
+
+    let props = &schema["properties"];
+    assert_eq!(props["currency"]["xml"], json!({ "attribute": true }));
+    assert_eq!(props["amountValue"]["xml"], json!({ "x-text": true }));
+    assert!(props["taxRate"].get("xml").is_none());
+    assert!(props.get("@currency").is_none());
+    assert!(props.get("$value").is_none());
+}
+
+#[test]
+fn test_xml_directive_on_struct_and_fields() {
+    let schema = extract_schema(
+        r#"
+        /// @openapi
+        /// @openapi xml name=order namespace=https://example.com/schema prefix=ex
+        pub struct Order {
+            /// @openapi xml name=item wrapped
+            pub items: Vec<String>,
+            /// @openapi xml attribute name=ref
+            /// description: Order reference
+            pub reference: String,
+        }
+        "#,
+        "Order",
+    );
+
+    assert_eq!(
+        schema["xml"],
+        json!({ "name": "order", "namespace": "https://example.com/schema", "prefix": "ex" })
+    );
+    let props = &schema["properties"];
+    // `name` names the items, the wrapper keeps the property name
+    assert_eq!(props["items"]["xml"], json!({ "wrapped": true }));
+    assert_eq!(props["items"]["items"]["xml"], json!({ "name": "item" }));
+    assert_eq!(
+        props["reference"]["xml"],
+        json!({ "name": "ref", "attribute": true })
+    );
+    assert_eq!(props["reference"]["description"], "Order reference");
+}